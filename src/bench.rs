@@ -0,0 +1,104 @@
+use std::time::{Duration, Instant};
+
+use push_swap::utils::N;
+use push_swap::stack::Stack;
+use push_swap::report::Report;
+use push_swap::solver::{solution, SolveStrategy, Rules};
+use push_swap::generator::{Rng, random_stack};
+
+use options::BenchConfig;
+
+struct Stats {
+    min: usize,
+    mean: f64,
+    p95: usize,
+    max: usize,
+    mean_time: Duration,
+    failures: usize,
+}
+
+pub fn bench<S: Stack<N>>(config: BenchConfig) {
+    let BenchConfig { sizes, samples, strategies, par_threads, seed } = config;
+
+    let mut rng = seed.map(Rng::new).unwrap_or_else(Rng::from_time);
+
+    println!("{:>6} {:<14} {:>8} {:>10} {:>8} {:>8} {:>12} {:>8}",
+        "size", "strategy", "min", "mean", "p95", "max", "time/solve", "failed");
+
+    for &size in &sizes {
+        // Every strategy is evaluated over the same inputs
+        let inputs = (0..samples)
+            .map(|_| random_stack(&mut rng, size))
+            .collect::<Vec<_>>();
+
+        for strategy in &strategies {
+            let runs = run_samples::<S>(strategy, par_threads, &inputs);
+
+            if let Some(stats) = compute_stats(runs) {
+                println!("{:>6} {:<14} {:>8} {:>10.2} {:>8} {:>8} {:>10.3}ms {:>8}",
+                    size, strategy.to_string(), stats.min, stats.mean, stats.p95, stats.max,
+                    as_millis(stats.mean_time), stats.failures);
+            }
+        }
+    }
+}
+
+pub struct Runs {
+    pub move_counts: Vec<usize>,
+    pub total_time: Duration,
+    // Solutions that do not leave the stacks sorted
+    pub failures: usize,
+}
+
+// Solves every input in-process and verifies the solutions with the checker
+pub fn run_samples<S: Stack<N>>(strategy: &SolveStrategy, par_threads: Option<usize>,
+                                inputs: &[Vec<N>]) -> Runs
+{
+    let mut runs = Runs {
+        move_counts: Vec::with_capacity(inputs.len()),
+        total_time: Duration::new(0, 0),
+        failures: 0,
+    };
+
+    for input in inputs {
+        let stack = input.iter().cloned().collect::<S>();
+
+        let start = Instant::now();
+        let instructions = solution(strategy, par_threads, &Rules::default(), stack.clone())
+            .expect("Every stack can be sorted")
+            .collect::<Vec<_>>();
+        runs.total_time += start.elapsed();
+
+        runs.move_counts.push(instructions.len());
+
+        if !Report::replay(stack, instructions, false).is_solved() {
+            runs.failures += 1
+        }
+    }
+
+    runs
+}
+
+fn compute_stats(runs: Runs) -> Option<Stats> {
+    let Runs { mut move_counts, total_time, failures } = runs;
+
+    if move_counts.is_empty() { return None }
+
+    move_counts.sort();
+
+    let samples = move_counts.len();
+    let p95_index = (samples * 95).div_ceil(100) - 1;
+
+    Some(Stats {
+        min: move_counts[0],
+        mean: move_counts.iter().sum::<usize>() as f64 / samples as f64,
+        p95: move_counts[p95_index],
+        max: move_counts[samples - 1],
+        mean_time: total_time / samples as u32,
+        failures,
+    })
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    duration.as_secs() as f64 * 1e3 + duration.subsec_nanos() as f64 / 1e6
+}
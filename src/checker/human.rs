@@ -0,0 +1,53 @@
+extern crate colored;
+
+use self::colored::Colorize;
+
+use push_swap::utils::N;
+use push_swap::stack::Stack;
+
+use push_swap::report::Report;
+use push_swap::costs::Costs;
+
+pub fn print<S: Stack<N>>(report: &Report<S>, costs: Option<&Costs>) {
+    let stack_display_width = report.initial.to_string().len();
+
+    println!("Start: {}", report.initial.to_string().blue().on_yellow());
+
+    for step in &report.trace {
+        println!("{} => {:width$} {} {}{}",
+            format!("{:3}", step.instr).purple(),
+            step.a.to_string(), "|".cyan(), step.b,
+            match step.dead_end {
+                true  => format!(" {}", "<- cannot end sorted from here".red()),
+                false => String::new()
+            },
+            width=stack_display_width
+        );
+    }
+
+    let sorted = match report.is_solved() {
+        true  => "Yes".green(),
+        false => "No".red()
+    };
+
+    println!("End: {}", report.a.to_string().on_green());
+    if report.b.len() > 0 {
+        println!("Left in B: {}", report.b.to_string().on_red());
+    }
+    println!("Sorted: {}", sorted);
+    println!("Moves: {}", report.moves.to_string().cyan());
+    println!("Ratio: {}", format!("{:.3}", report.ratio()).cyan());
+    if let Some(costs) = costs {
+        println!("Cost: {}", report.cost(costs).to_string().cyan());
+    }
+
+    let histogram = report.histogram.iter()
+        .filter(|(_, count)| *count > 0)
+        .map(|(instr, count)| format!("{} {}", instr, count.to_string().cyan()))
+        .collect::<Vec<_>>();
+
+    println!("Instructions: {}", histogram.join(", "));
+    println!("Max B size: {}", report.max_b_len.to_string().cyan());
+    println!("Wasted pairs: {}", report.wasted_pairs.to_string().yellow());
+    println!("Fusable pairs: {}", report.fusable_pairs.to_string().yellow());
+}
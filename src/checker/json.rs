@@ -0,0 +1,70 @@
+use std::fmt::{self, Write};
+
+use push_swap::utils::{N, to_vec};
+use push_swap::stack::Stack;
+
+use push_swap::report::Report;
+use push_swap::costs::Costs;
+
+pub fn print<S: Stack<N>>(report: &Report<S>, costs: &Costs) {
+    let mut out = String::new();
+    write_report(&mut out, report, costs).unwrap();
+    println!("{}", out);
+}
+
+fn write_report<S: Stack<N>>(out: &mut String, report: &Report<S>, costs: &Costs)
+    -> fmt::Result
+{
+    write!(out, "{{\"initial\":")?;
+    write_stack(out, &report.initial)?;
+    write!(out, ",\"a\":")?;
+    write_stack(out, &report.a)?;
+    write!(out, ",\"b\":")?;
+    write_stack(out, &report.b)?;
+
+    write!(out, ",\"sorted\":{}", report.is_sorted())?;
+    write!(out, ",\"ok\":{}", report.is_solved())?;
+    write!(out, ",\"moves\":{}", report.moves)?;
+    write!(out, ",\"cost\":{}", report.cost(costs))?;
+
+    write!(out, ",\"histogram\":{{")?;
+    for (i, (instr, count)) in report.histogram.iter().enumerate() {
+        if i > 0 { write!(out, ",")? }
+        write!(out, "\"{}\":{}", instr, count)?;
+    }
+    write!(out, "}}")?;
+
+    write!(out, ",\"max_b_len\":{}", report.max_b_len)?;
+    write!(out, ",\"wasted_pairs\":{}", report.wasted_pairs)?;
+    write!(out, ",\"fusable_pairs\":{}", report.fusable_pairs)?;
+
+    // NaN and infinities are not representable in JSON
+    match report.ratio() {
+        ratio if ratio.is_finite() => write!(out, ",\"ratio\":{}", ratio)?,
+        _                          => write!(out, ",\"ratio\":null")?,
+    }
+
+    if !report.trace.is_empty() {
+        write!(out, ",\"trace\":[")?;
+        for (i, step) in report.trace.iter().enumerate() {
+            if i > 0 { write!(out, ",")? }
+            write!(out, "{{\"instr\":\"{}\",\"a\":", step.instr)?;
+            write_stack(out, &step.a)?;
+            write!(out, ",\"b\":")?;
+            write_stack(out, &step.b)?;
+            write!(out, ",\"dead_end\":{}}}", step.dead_end)?;
+        }
+        write!(out, "]")?;
+    }
+
+    write!(out, "}}")
+}
+
+fn write_stack<S: Stack<N>>(out: &mut String, stack: &S) -> fmt::Result {
+    write!(out, "[")?;
+    for (i, n) in to_vec(stack).iter().enumerate() {
+        if i > 0 { write!(out, ",")? }
+        write!(out, "{}", n)?;
+    }
+    write!(out, "]")
+}
@@ -0,0 +1,128 @@
+use push_swap::utils::*;
+
+use std::io::{self, BufReader, BufRead, Read};
+use std::process::exit;
+use std::str::FromStr;
+
+use push_swap::stack::Stack;
+use push_swap::instruction::{Instruction, InstructionSet, InvalidInstruction};
+use push_swap::report::Report;
+
+use options::{CheckConfig, OutputFormat};
+
+mod human;
+mod json;
+mod stepper;
+mod multi;
+
+pub fn check<S: Stack<N>>(config: CheckConfig) -> ! {
+    if let Some(aux_stacks) = config.aux_stacks {
+        if config.costs.is_some() || config.allowed.is_some() || config.step || config.debug_states {
+            fail(config.strict,
+                 "--costs, --allowed, --step and --debug-states only apply to the two stacks puzzle")
+        }
+
+        multi::check::<S>(config, aux_stacks)
+    }
+
+    if config.strict {
+        check_strict::<S>(config)
+    }
+
+    let ranked = match config.target.ranks(&config.raw_stack) {
+        Ok(ranks) => ranks.into_iter().collect::<S>(),
+        Err(err)  => fail(false, &err)
+    };
+    let stack = config.raw_stack.into_iter().collect::<S>();
+
+    let allowed = config.allowed.unwrap_or_default();
+    let instructions = read_instructions(io::stdin(), false)
+        .map(|instr| match only_allowed(instr, &allowed) {
+            Ok(instr) => instr,
+            Err(err)  => fail(false, &format!("Invalid instruction: {}", err.0))
+        })
+        .collect();
+
+    if config.step {
+        stepper::run(stack, instructions);
+        exit(0)
+    }
+
+    let report = Report::replay_ranked(stack, ranked, instructions, config.debug_states);
+
+    match config.format {
+        OutputFormat::Human => human::print(&report, config.costs.as_ref()),
+        OutputFormat::Json  => json::print(&report, &config.costs.unwrap_or_default()),
+    }
+
+    exit(if report.is_solved() { 0 } else { 1 });
+}
+
+// Mimics the reference checker: prints exactly `OK`, `KO` or `Error`
+fn check_strict<S: Stack<N>>(config: CheckConfig) -> ! {
+    fn error() -> ! {
+        eprintln!("Error");
+        exit(1)
+    }
+
+    // Only whether the target is reached matters, which the ranks tell.
+    // Duplicates are rejected along the way
+    let mut stack = match config.target.ranks(&config.raw_stack) {
+        Ok(ranks) => ranks.into_iter().collect::<S>(),
+        Err(_)    => error()
+    };
+    let mut side_stack = stack.empty_like();
+    let allowed = config.allowed.unwrap_or_default();
+
+    for instr in read_instructions(io::stdin(), true) {
+        match only_allowed(instr, &allowed) {
+            Ok(instr) => execute(&instr, &mut (&mut stack, &mut side_stack)),
+            Err(_)    => error()
+        }
+    }
+
+    match is_solved(&stack, &side_stack) {
+        true  => { println!("OK"); exit(0) },
+        false => { println!("KO"); exit(1) }
+    }
+}
+
+// Strict mode keeps to the reference checker, which only ever prints `Error`
+fn fail(strict: bool, message: &str) -> ! {
+    match strict {
+        true  => eprintln!("Error"),
+        false => eprintln!("{}", message),
+    }
+
+    exit(1)
+}
+
+// Instructions left out of the puzzle variant are as invalid as unknown ones
+fn only_allowed(instr: Result<Instruction, InvalidInstruction>, allowed: &InstructionSet)
+    -> Result<Instruction, InvalidInstruction>
+{
+    match instr {
+        Ok(instr) if !allowed.contains(&instr) => Err(InvalidInstruction(instr.to_string())),
+        other                                  => other,
+    }
+}
+
+// In strict mode lines are parsed verbatim: blank lines and surrounding
+// whitespace are treated as invalid instructions
+pub fn read_instructions<T>(reader: impl Read, strict: bool)
+    -> impl Iterator<Item = Result<T, InvalidInstruction>>
+where
+    T: FromStr<Err = InvalidInstruction>
+{
+    BufReader::new(reader).lines()
+        .filter_map(move |line_result| {
+            let line = line_result
+                .expect("Failed to read line");
+
+            match (strict, line.trim()) {
+                (true, _)      => Some(line.parse()),
+                (false, "")    => None,
+                (false, instr) => Some(instr.parse())
+            }
+        })
+}
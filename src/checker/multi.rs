@@ -0,0 +1,86 @@
+extern crate colored;
+
+use self::colored::Colorize;
+
+use std::io;
+use std::process::exit;
+
+use push_swap::utils::{N, to_vec};
+use push_swap::stack::Stack;
+use push_swap::multi::{MultiInstruction, MAX_STACKS, execute_multi, is_solved_multi};
+
+use options::{CheckConfig, OutputFormat};
+
+use super::{read_instructions, fail};
+
+// Checks instructions of the generalized puzzle, the values being sorted
+// on A once every auxiliary stack is empty
+pub fn check<S: Stack<N>>(config: CheckConfig, aux_stacks: usize) -> ! {
+    let strict = config.strict;
+
+    let k = aux_stacks + 1;
+    if aux_stacks == 0 || k > MAX_STACKS {
+        fail(strict, &format!("Between 1 and {} auxiliary stacks are supported", MAX_STACKS - 1))
+    }
+
+    let ranks = match config.target.ranks(&config.raw_stack) {
+        Ok(ranks) => ranks,
+        Err(err)  => fail(strict, &err)
+    };
+
+    let stack = config.raw_stack.into_iter().collect::<S>();
+    let mut stacks = vec![stack.empty_like(); k];
+    let mut ranked = vec![stack.empty_like(); k];
+    stacks[0] = stack;
+    ranked[0] = ranks.into_iter().collect();
+
+    let mut moves = 0;
+
+    for instr in read_instructions::<MultiInstruction>(io::stdin(), strict) {
+        match instr {
+            Ok(ref instr) if instr.stacks() <= k => {
+                execute_multi(instr, &mut stacks);
+                execute_multi(instr, &mut ranked);
+                moves += 1;
+            },
+            Ok(instr) => fail(strict, &format!("Invalid instruction: {}", instr)),
+            Err(err)  => fail(strict, &format!("Invalid instruction: {}", err.0)),
+        }
+    }
+
+    let solved = is_solved_multi(&ranked);
+
+    match (strict, &config.format) {
+        (true, _)                    => println!("{}", if solved { "OK" } else { "KO" }),
+        (false, OutputFormat::Human) => print_human(&stacks, solved, moves),
+        (false, OutputFormat::Json)  => print_json(&stacks, solved, moves),
+    }
+
+    exit(if solved { 0 } else { 1 })
+}
+
+fn print_human<S: Stack<N>>(stacks: &[S], solved: bool, moves: usize) {
+    for (index, stack) in stacks.iter().enumerate() {
+        let name = (b'A' + index as u8) as char;
+        println!("{}: {}", name, stack);
+    }
+
+    let sorted = match solved {
+        true  => "Yes".green(),
+        false => "No".red()
+    };
+
+    println!("Sorted: {}", sorted);
+    println!("Moves: {}", moves.to_string().cyan());
+}
+
+fn print_json<S: Stack<N>>(stacks: &[S], solved: bool, moves: usize) {
+    let stacks = stacks.iter()
+        .map(|stack| {
+            let values = to_vec(stack).iter().map(|n| n.to_string()).collect::<Vec<_>>();
+            format!("[{}]", values.join(","))
+        })
+        .collect::<Vec<_>>();
+
+    println!("{{\"stacks\":[{}],\"ok\":{},\"moves\":{}}}", stacks.join(","), solved, moves);
+}
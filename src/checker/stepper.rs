@@ -0,0 +1,193 @@
+extern crate colored;
+extern crate termion;
+
+use self::colored::Colorize;
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::fmt;
+
+use push_swap::utils::N;
+use push_swap::stack::{Stack, Moved};
+use push_swap::replay::Replay;
+use push_swap::instruction::Instruction;
+
+pub enum Breakpoint {
+    // Before the instruction at this index gets executed
+    Instruction(usize),
+    TopOfA(N),
+    BExceeds(usize),
+}
+
+impl Breakpoint {
+    fn hit<S: Stack<N>>(&self, replay: &Replay<S>) -> bool {
+        match *self {
+            Breakpoint::Instruction(index) => replay.cursor() == index,
+            Breakpoint::TopOfA(value)      => replay.a().get(0) == Some(&value),
+            Breakpoint::BExceeds(size)     => replay.b().len() > size,
+        }
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Breakpoint::Instruction(index) => write!(f, "instruction #{}", index),
+            Breakpoint::TopOfA(value)      => write!(f, "{} on top of A", value),
+            Breakpoint::BExceeds(size)     => write!(f, "B larger than {}", size),
+        }
+    }
+}
+
+enum Command {
+    Forward(usize),
+    Backward(usize),
+    Continue,
+    ReverseContinue,
+    Break(Breakpoint),
+    Delete(Option<usize>),
+    List,
+    Help,
+    Quit,
+}
+
+const HELP: &str = "\
+n [k]          step forward k instructions (default 1)
+p [k]          step backward k instructions (default 1)
+c              continue until a breakpoint or the end
+rc             continue backwards until a breakpoint or the start
+b <index>      break before the instruction at <index>
+b top <value>  break when <value> reaches the top of A
+b size <n>     break when B holds more than <n> values
+d [id]         delete a breakpoint (all of them by default)
+l              list breakpoints
+q              quit";
+
+// Interactive session over the instructions. Commands are read from the
+// terminal since stdin already carries the instructions
+pub fn run<S: Stack<N>>(stack: S, instructions: Vec<Instruction>) {
+    let tty = termion::get_tty()
+        .expect("Failed to open the terminal");
+    let mut commands = BufReader::new(tty).lines();
+
+    let mut replay = Replay::new(stack, instructions);
+    let mut breakpoints = Vec::new();
+
+    print_state(&replay);
+
+    loop {
+        print!("{} ", "(step)".cyan());
+        io::stdout().flush().unwrap();
+
+        let line = match commands.next() {
+            Some(Ok(line)) => line,
+            _              => break,
+        };
+
+        let command = match parse_command(&line) {
+            Some(command) => command,
+            None          => { println!("Invalid command, type `h` for help"); continue }
+        };
+
+        let before = replay.snapshot();
+
+        match command {
+            Command::Forward(k)  => replay.seek(replay.cursor() + k),
+            Command::Backward(k) => replay.seek(replay.cursor().saturating_sub(k)),
+
+            Command::Continue => {
+                replay.forward();
+                while !replay.is_over() && !any_hit(&breakpoints, &replay) {
+                    replay.forward()
+                }
+            },
+            Command::ReverseContinue => {
+                replay.backward();
+                while replay.cursor() > 0 && !any_hit(&breakpoints, &replay) {
+                    replay.backward()
+                }
+            },
+
+            Command::Break(breakpoint) => {
+                println!("Breakpoint {}: {}", breakpoints.len(), breakpoint);
+                breakpoints.push(breakpoint);
+                continue
+            },
+            Command::Delete(Some(id)) => {
+                if id < breakpoints.len() { breakpoints.remove(id); }
+                continue
+            },
+            Command::Delete(None) => { breakpoints.clear(); continue },
+            Command::List => {
+                for (id, breakpoint) in breakpoints.iter().enumerate() {
+                    println!("{}: {}", id, breakpoint);
+                }
+                continue
+            },
+            Command::Help => { println!("{}", HELP); continue },
+            Command::Quit => break,
+        }
+
+        for (id, breakpoint) in breakpoints.iter().enumerate() {
+            if breakpoint.hit(&replay) {
+                println!("{}", format!("Breakpoint {} hit: {}", id, breakpoint).yellow());
+            }
+        }
+        print_state(&replay);
+        print_moves(&before.diff(&replay.snapshot()));
+    }
+}
+
+fn any_hit<S: Stack<N>>(breakpoints: &[Breakpoint], replay: &Replay<S>) -> bool {
+    breakpoints.iter().any(|breakpoint| breakpoint.hit(replay))
+}
+
+fn print_state<S: Stack<N>>(replay: &Replay<S>) {
+    let last_instr = replay.last_instruction()
+        .map(|instr| instr.to_string())
+        .unwrap_or_else(|| String::from("-"));
+
+    println!("[{}/{}] {} => {} {} {}",
+        replay.cursor(), replay.len(),
+        format!("{:3}", last_instr).purple(),
+        replay.a(), "|".cyan(), replay.b()
+    );
+}
+
+// Only worth reading for a handful of values, as after single steps
+fn print_moves(moves: &[Moved]) {
+    const MAX_SHOWN: usize = 8;
+
+    if moves.is_empty() || moves.len() > MAX_SHOWN { return }
+
+    let moves = moves.iter()
+        .map(|moved| moved.to_string())
+        .collect::<Vec<_>>();
+
+    println!("  {}", moves.join(", ").dimmed());
+}
+
+fn parse_command(line: &str) -> Option<Command> {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    let count = |word: Option<&&str>| match word {
+        Some(word) => word.parse().ok(),
+        None       => Some(1),
+    };
+
+    let command = match words.as_slice() {
+        [] | ["n"] | ["n", _] | ["next"] | ["next", _] => Command::Forward(count(words.get(1))?),
+        ["p"] | ["p", _] | ["prev"] | ["prev", _]      => Command::Backward(count(words.get(1))?),
+        ["c"]  | ["continue"]                          => Command::Continue,
+        ["rc"] | ["reverse-continue"]                  => Command::ReverseContinue,
+        ["b", "top", value]                            => Command::Break(Breakpoint::TopOfA(value.parse().ok()?)),
+        ["b", "size", size]                            => Command::Break(Breakpoint::BExceeds(size.parse().ok()?)),
+        ["b", index]                                   => Command::Break(Breakpoint::Instruction(index.parse().ok()?)),
+        ["d"]                                          => Command::Delete(None),
+        ["d", id]                                      => Command::Delete(Some(id.parse().ok()?)),
+        ["l"] | ["list"]                               => Command::List,
+        ["h"] | ["help"]                               => Command::Help,
+        ["q"] | ["quit"]                               => Command::Quit,
+        _                                              => return None,
+    };
+
+    Some(command)
+}
@@ -0,0 +1,64 @@
+use std::str::FromStr;
+
+use instruction::Instruction;
+
+// Weight of every instruction, indexed like `Instruction::ALL`. Exact
+// solvers minimize the total cost of their solution rather than its length
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Costs([u32; 11]);
+
+impl Default for Costs {
+    fn default() -> Self {
+        Costs([1; 11])
+    }
+}
+
+impl Costs {
+    pub fn of(&self, instr: &Instruction) -> u32 {
+        self.0[instr.clone() as usize]
+    }
+
+    pub fn set(&mut self, instr: &Instruction, cost: u32) {
+        self.0[instr.clone() as usize] = cost
+    }
+
+    // Whether every instruction costs the same, minimizing the cost then
+    // amounts to minimizing the number of moves
+    pub fn is_uniform(&self) -> bool {
+        self.0.iter().all(|cost| *cost == self.0[0])
+    }
+
+    pub fn total<'a>(&self, instructions: impl IntoIterator<Item = &'a Instruction>) -> u64 {
+        instructions.into_iter()
+            .map(|instr| self.of(instr) as u64)
+            .sum()
+    }
+}
+
+// One `<instruction> <cost>` pair per line, unlisted instructions cost 1
+impl FromStr for Costs {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut costs = Costs::default();
+
+        let lines = s.lines()
+            .map(|line| line.split('#').next().unwrap_or("").trim())
+            .filter(|line| !line.is_empty());
+
+        for line in lines {
+            let invalid = || format!("invalid cost: {}", line);
+
+            let mut words = line.split_whitespace();
+            let (instr, cost) = match (words.next(), words.next(), words.next()) {
+                (Some(instr), Some(cost), None) => (instr, cost),
+                _                               => return Err(invalid()),
+            };
+
+            let instr = instr.parse::<Instruction>().map_err(|_| invalid())?;
+            costs.set(&instr, cost.parse().map_err(|_| invalid())?);
+        }
+
+        Ok(costs)
+    }
+}
@@ -0,0 +1,46 @@
+use std::fs::File;
+use std::io::Write;
+use std::process::exit;
+
+use push_swap::generator::{Rng, pick_values, arrange};
+
+use options::GenerateConfig;
+
+pub fn generate(config: GenerateConfig) {
+    let GenerateConfig {
+        size, seed, min, max, signed, distribution, swaps, rotation, output
+    } = config;
+
+    // Defaults to a contiguous range of values, centered on zero if signed
+    let default_min = match signed {
+        true  => -(size as i64 / 2),
+        false => 0,
+    };
+    let min = min.unwrap_or(default_min);
+    let max = max.or_else(|| min.checked_add(size as i64 - 1));
+
+    let mut rng = seed.map(Rng::new).unwrap_or_else(Rng::from_time);
+
+    let picked = match max {
+        Some(max) => pick_values(&mut rng, size, min, max),
+        None      => Err(format!("Cannot pick {} distinct values from {}", size, min)),
+    };
+
+    let mut values = picked.unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1)
+    });
+    arrange(&mut rng, &mut values, distribution, swaps, rotation);
+
+    let line = values.iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    match output {
+        Some(path) => File::create(path)
+            .and_then(|mut file| writeln!(file, "{}", line))
+            .expect("Failed to write the output file"),
+        None => println!("{}", line),
+    }
+}
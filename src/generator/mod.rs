@@ -0,0 +1,120 @@
+use std::str::FromStr;
+
+mod rng;
+
+pub use self::rng::Rng;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Distribution {
+    Uniform, NearlySorted, Reversed, Rotated, WorstCase
+}
+
+// Uniformly shuffled stack of the values 0..size
+pub fn random_stack(rng: &mut Rng, size: usize) -> Vec<i64> {
+    let mut values = (0..size as i64).collect::<Vec<_>>();
+    rng.shuffle(&mut values);
+    values
+}
+
+// `size` distinct values of [min, max], in ascending order
+pub fn pick_values(rng: &mut Rng, size: usize, min: i64, max: i64) -> Result<Vec<i64>, String> {
+    use std::collections::BTreeSet;
+
+    let too_few = || format!("Cannot pick {} distinct values in [{}, {}]", size, min, max);
+
+    if min > max {
+        return Err(too_few())
+    }
+
+    // The whole range of i64 holds one value too many for a u64 span, any
+    // u64 then stands for a value of the range
+    let span = max.abs_diff(min).checked_add(1);
+
+    if span.is_some_and(|span| span < size as u64) {
+        return Err(too_few())
+    }
+
+    if size == 0 { return Ok(Vec::new()) }
+
+    // Dense ranges are cheaper to shuffle than to sample
+    if span.is_some_and(|span| span <= 4 * size as u64) {
+        let mut values = (min..=max).collect::<Vec<_>>();
+        rng.shuffle(&mut values);
+        values.truncate(size);
+        values.sort();
+        return Ok(values)
+    }
+
+    let mut values = BTreeSet::new();
+    while values.len() < size {
+        // Offsets past i64::MAX wrap around to the right value
+        let offset = match span {
+            Some(span) => rng.below(span),
+            None       => rng.next_u64(),
+        };
+        values.insert(min.wrapping_add(offset as i64));
+    }
+
+    Ok(values.into_iter().collect())
+}
+
+pub fn arrange(rng: &mut Rng, values: &mut [i64], distribution: Distribution,
+           swaps: usize, rotation: Option<usize>)
+{
+    // A single value has a single arrangement
+    let len = values.len();
+    if len < 2 { return }
+
+    match distribution {
+        Distribution::Uniform => rng.shuffle(values),
+
+        // Each swap exchanges two distinct positions
+        Distribution::NearlySorted => for _ in 0..swaps {
+            let i = rng.below(len as u64);
+            let j = (i + 1 + rng.below(len as u64 - 1)) % len as u64;
+            values.swap(i as usize, j as usize)
+        },
+
+        Distribution::Reversed => values.reverse(),
+
+        Distribution::Rotated => {
+            // A random rotation never leaves the values sorted
+            let rotation = rotation.unwrap_or_else(|| 1 + rng.below(len as u64 - 1) as usize);
+            values.rotate_left(rotation % len)
+        },
+
+        // Bit-reversal permutation: neighbours hold values as far apart as
+        // possible, which forces insertion based solvers into long rotations
+        Distribution::WorstCase => {
+            let bits = (len as u64).next_power_of_two().trailing_zeros();
+            let reverse_bits = |i: u64| match bits {
+                0 => 0,
+                _ => i.reverse_bits() >> (64 - bits),
+            };
+
+            let sorted = values.to_vec();
+            let ranks = (0..(1 << bits) as u64)
+                .map(reverse_bits)
+                .filter(|&rank| rank < len as u64);
+
+            for (value, rank) in values.iter_mut().zip(ranks) {
+                *value = sorted[rank as usize];
+            }
+        },
+    }
+}
+
+impl FromStr for Distribution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "uniform"       | "shuffle" => Ok(Distribution::Uniform),
+            "nearly-sorted" | "nearly"  => Ok(Distribution::NearlySorted),
+            "reversed"      | "reverse" => Ok(Distribution::Reversed),
+            "rotated"       | "rotate"  => Ok(Distribution::Rotated),
+            "worst-case"    | "worst"   => Ok(Distribution::WorstCase),
+            invalid => Err(String::from(invalid))
+        }
+    }
+}
@@ -0,0 +1,151 @@
+extern crate colored;
+
+use self::colored::Colorize;
+
+use std::fs;
+use std::process::exit;
+use std::str::FromStr;
+
+use push_swap::utils::N;
+use push_swap::stack::Stack;
+use bench::run_samples;
+use push_swap::generator::{Rng, random_stack};
+
+use options::GradeConfig;
+
+// One line per stack size followed by its thresholds, a move count scores
+// the points of the best threshold it satisfies (0 if none)
+const DEFAULT_TABLE: &str = "\
+3    <=3:5
+5    <=12:5
+100  <700:5  <900:4  <1100:3  <1300:2  <1500:1
+500  <5500:5 <7000:4 <8500:3  <10000:2 <11500:1
+";
+
+enum Threshold {
+    Below(usize),
+    AtMost(usize),
+}
+
+struct Rule {
+    threshold: Threshold,
+    points: u32,
+}
+
+struct GradingTable(Vec<(usize, Vec<Rule>)>);
+
+impl GradingTable {
+    fn max_points(rules: &[Rule]) -> u32 {
+        rules.iter().map(|rule| rule.points).max().unwrap_or(0)
+    }
+
+    fn points(rules: &[Rule], moves: usize) -> u32 {
+        rules.iter()
+            .filter(|rule| match rule.threshold {
+                Threshold::Below(max)  => moves < max,
+                Threshold::AtMost(max) => moves <= max,
+            })
+            .map(|rule| rule.points)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl FromStr for GradingTable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_rule = |rule: &str| -> Result<Rule, String> {
+            let invalid = || format!("invalid rule: {}", rule);
+
+            let mut parts = rule.splitn(2, ':');
+            let (threshold, points) = match (parts.next(), parts.next()) {
+                (Some(threshold), Some(points)) => (threshold, points),
+                _                               => return Err(invalid()),
+            };
+
+            let threshold = match threshold {
+                t if t.starts_with("<=") => t[2..].parse().map(Threshold::AtMost),
+                t if t.starts_with('<')  => t[1..].parse().map(Threshold::Below),
+                _                        => return Err(invalid()),
+            };
+
+            Ok(Rule {
+                threshold: threshold.map_err(|_| invalid())?,
+                points: points.parse().map_err(|_| invalid())?,
+            })
+        };
+
+        let lines = s.lines()
+            .map(|line| line.split('#').next().unwrap_or("").trim())
+            .filter(|line| !line.is_empty());
+
+        let mut table = Vec::new();
+
+        for line in lines {
+            let mut words = line.split_whitespace();
+            let size = words.next()
+                .and_then(|size| size.parse().ok())
+                .ok_or_else(|| format!("invalid size: {}", line))?;
+            let rules = words.map(parse_rule).collect::<Result<_, _>>()?;
+
+            table.push((size, rules));
+        }
+
+        Ok(GradingTable(table))
+    }
+}
+
+pub fn grade<S: Stack<N>>(config: GradeConfig) {
+    let GradeConfig { strategy, samples, table, par_threads, seed } = config;
+
+    let table_source = match table {
+        Some(path) => fs::read_to_string(path).expect("Failed to read the grading table"),
+        None       => String::from(DEFAULT_TABLE),
+    };
+    let GradingTable(table) = table_source.parse()
+        .unwrap_or_else(|err: String| { eprintln!("{}", err); exit(1) });
+
+    let mut rng = seed.map(Rng::new).unwrap_or_else(Rng::from_time);
+    let mut passed = true;
+    let (mut total, mut total_max) = (0, 0);
+
+    println!("Strategy: {} ({} samples per size)", strategy, samples);
+    println!("{:>6} {:>10} {:>8} {:>10} {:>10} {:>8}",
+        "size", "mean", "worst", "mean pts", "worst pts", "failed");
+
+    for (size, rules) in &table {
+        let inputs = (0..samples)
+            .map(|_| random_stack(&mut rng, *size))
+            .collect::<Vec<_>>();
+
+        let runs = run_samples::<S>(&strategy, par_threads, &inputs);
+
+        let worst = runs.move_counts.iter().cloned().max().unwrap_or(0);
+        let mean = runs.move_counts.iter().sum::<usize>() as f64
+                 / ::std::cmp::max(1, runs.move_counts.len()) as f64;
+
+        let max_points = GradingTable::max_points(rules);
+        let worst_points = GradingTable::points(rules, worst);
+        let mean_points = GradingTable::points(rules, mean.ceil() as usize);
+
+        // The worst sample is what an evaluation could stumble upon
+        let score = format!("{}/{}", worst_points, max_points);
+        let score = match worst_points {
+            _ if worst_points == 0 || runs.failures > 0 => score.red(),
+            points if points == max_points              => score.green(),
+            _                                           => score.yellow(),
+        };
+
+        println!("{:>6} {:>10.2} {:>8} {:>10} {:>10} {:>8}",
+            size, mean, worst, format!("{}/{}", mean_points, max_points), score, runs.failures);
+
+        passed &= worst_points > 0 && runs.failures == 0;
+        total += worst_points;
+        total_max += max_points;
+    }
+
+    println!("Total: {}/{}", total, total_max);
+
+    exit(if passed { 0 } else { 1 })
+}
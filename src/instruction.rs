@@ -9,8 +9,54 @@ pub enum Instruction {
     RRotateA, RRotateB, RRotateBoth,
 }
 
+impl Instruction {
+    pub const ALL: [Instruction; 11] = [
+        Instruction::PushA, Instruction::PushB,
+        Instruction::SwapA, Instruction::SwapB, Instruction::SwapBoth,
+        Instruction::RotateA, Instruction::RotateB, Instruction::RotateBoth,
+        Instruction::RRotateA, Instruction::RRotateB, Instruction::RRotateBoth,
+    ];
+
+    // The instruction undoing `self`. Only holds for pushes if the pushed
+    // from stack was not empty
+    pub fn inverse(&self) -> Instruction {
+        use self::Instruction::*;
+
+        match self {
+            PushA       => PushB,
+            PushB       => PushA,
+            SwapA       => SwapA,
+            SwapB       => SwapB,
+            SwapBoth    => SwapBoth,
+            RotateA     => RRotateA,
+            RotateB     => RRotateB,
+            RotateBoth  => RRotateBoth,
+            RRotateA    => RotateA,
+            RRotateB    => RotateB,
+            RRotateBoth => RotateBoth,
+        }
+    }
+
+    // Whether executing `self` then `next` is a no-op
+    pub fn cancels(&self, next: &Instruction) -> bool {
+        &self.inverse() == next
+    }
+
+    // The single instruction equivalent to executing `self` then `next`
+    pub fn fuse(&self, next: &Instruction) -> Option<Instruction> {
+        use self::Instruction::*;
+
+        match (self, next) {
+            (SwapA, SwapB)       | (SwapB, SwapA)       => Some(SwapBoth),
+            (RotateA, RotateB)   | (RotateB, RotateA)   => Some(RotateBoth),
+            (RRotateA, RRotateB) | (RRotateB, RRotateA) => Some(RRotateBoth),
+            _ => None
+        }
+    }
+}
+
 #[derive(Debug)]
-pub struct InvalidInstruction(String);
+pub struct InvalidInstruction(pub String);
 
 impl FromStr for Instruction {
     type Err = InvalidInstruction;
@@ -56,3 +102,47 @@ impl fmt::Display for Instruction {
         write!(f, "{}", as_string)
     }
 }
+
+// Instructions a puzzle variant permits, indexed like `Instruction::ALL`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InstructionSet([bool; 11]);
+
+impl Default for InstructionSet {
+    fn default() -> Self {
+        InstructionSet([true; 11])
+    }
+}
+
+impl InstructionSet {
+    pub fn contains(&self, instr: &Instruction) -> bool {
+        self.0[instr.clone() as usize]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static Instruction> + '_ {
+        Instruction::ALL.iter().filter(move |instr| self.contains(instr))
+    }
+}
+
+// Comma separated instructions, e.g. `sa,pb,pa,ra,rra`
+impl FromStr for InstructionSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = InstructionSet([false; 11]);
+
+        for instr in s.split(',').map(str::trim).filter(|instr| !instr.is_empty()) {
+            let instr = instr.parse::<Instruction>()
+                .map_err(|InvalidInstruction(instr)| format!("unknown instruction '{}'", instr))?;
+            set.0[instr as usize] = true;
+        }
+
+        Ok(set)
+    }
+}
+
+impl fmt::Display for InstructionSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let instrs = self.iter().map(|instr| instr.to_string()).collect::<Vec<_>>();
+        write!(f, "{}", instrs.join(","))
+    }
+}
@@ -0,0 +1,83 @@
+pub mod stack;
+pub mod instruction;
+pub mod utils;
+pub mod solver;
+pub mod report;
+pub mod replay;
+pub mod generator;
+pub mod costs;
+pub mod target;
+pub mod multi;
+
+pub use stack::{Stack, StackPair, LLStack, VDStack, VecStack, RingStack, SmallStack, SharedStacks};
+pub use instruction::{Instruction, InstructionSet};
+pub use solver::{SolveStrategy, Rules};
+pub use costs::Costs;
+pub use target::Target;
+pub use multi::MultiInstruction;
+pub use utils::execute;
+
+use solver::solution;
+use report::Report;
+use utils::{N, to_vec};
+
+// Puzzle variant the entry points below work with, the classic one by default
+#[derive(Debug, Clone, Default)]
+pub struct Variant {
+    pub target: Target,
+    pub rules: Rules,
+}
+
+pub struct CheckResult {
+    pub solved: bool,
+    pub moves: usize,
+    pub cost: u64,
+    pub a: Vec<i64>,
+    pub b: Vec<i64>,
+}
+
+// Solvers work on ranks, any distinct values can be handed to them
+pub fn solve(strategy: &SolveStrategy, values: &[i64], variant: &Variant)
+    -> Result<Vec<Instruction>, String>
+{
+    let stack = ranks(values, &variant.target)?.into_iter().collect::<VDStack<N>>();
+
+    Ok(solution(strategy, None, &variant.rules, stack)?.collect())
+}
+
+pub fn check(values: &[i64], instructions: &[Instruction], variant: &Variant)
+    -> Result<CheckResult, String>
+{
+    if let Some(instr) = instructions.iter().find(|instr| !variant.rules.allowed.contains(instr)) {
+        return Err(format!("{} is not allowed", instr))
+    }
+
+    let ranked = ranks(values, &variant.target)?;
+    let stack = ranked.iter().cloned().collect::<VDStack<N>>();
+    let report = Report::replay(stack, instructions.to_vec(), false);
+
+    let mut by_rank = vec![0; values.len()];
+    for (rank, value) in ranked.iter().zip(values) {
+        by_rank[*rank as usize] = *value;
+    }
+    let unrank = |stack: &VDStack<N>| to_vec(stack).into_iter()
+        .map(|rank| by_rank[rank as usize])
+        .collect();
+
+    Ok(CheckResult {
+        solved: report.is_solved(),
+        moves: report.moves,
+        cost: report.cost(&variant.rules.costs),
+        a: unrank(&report.a),
+        b: unrank(&report.b),
+    })
+}
+
+// Places of the values in the target order, from 0
+fn ranks(values: &[i64], target: &Target) -> Result<Vec<N>, String> {
+    let places = target.ranks(values)?;
+    let mut order = places.clone();
+    order.sort();
+
+    Ok(places.iter().map(|place| order.binary_search(place).unwrap() as N).collect())
+}
@@ -1,20 +1,25 @@
-#![feature(generators, generator_trait)]
-#![feature(iterator_find_map)]
-
 #[macro_use] extern crate structopt;
+extern crate push_swap;
 
 mod options;
-mod stack;
-mod instruction;
 mod checker;
-mod solver;
-mod utils;
+mod solve;
+mod visualizer;
+mod render;
+mod generate;
+mod bench;
+mod grade;
 
 use options::*;
 use checker::check;
-use stack::*;
-use solver::solve;
-use utils::N;
+use push_swap::stack::*;
+use solve::solve;
+use visualizer::visualize;
+use render::render;
+use generate::generate;
+use bench::bench;
+use grade::grade;
+use push_swap::utils::N;
 
 fn main() {
     use StackType::*;
@@ -26,6 +31,8 @@ fn main() {
         LinkedList => run_with_stack_type::<LLStack<N>>,
         VecDeque   => run_with_stack_type::<VDStack<N>>,
         Vec        => run_with_stack_type::<VecStack<N>>,
+        Ring       => run_with_stack_type::<RingStack<N>>,
+        Small      => run_with_stack_type::<SmallStack<N>>,
     };
 
     run_command(command)
@@ -33,7 +40,12 @@ fn main() {
 
 fn run_with_stack_type<S: Stack<N>>(command: Command) {
     match command {
-        Command::Check(config) => check::<S>(config),
-        Command::Solve(config) => solve::<S>(config),
+        Command::Check(config)     => check::<S>(config),
+        Command::Solve(config)     => solve::<S>(config),
+        Command::Visualize(config) => visualize::<S>(config),
+        Command::Render(config)    => render::<S>(config),
+        Command::Generate(config)  => generate(config),
+        Command::Bench(config)     => bench::<S>(config),
+        Command::Grade(config)     => grade::<S>(config),
     }
 }
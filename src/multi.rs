@@ -0,0 +1,123 @@
+use std::str::FromStr;
+use std::fmt;
+
+use utils::N;
+use stack::Stack;
+use instruction::InvalidInstruction;
+
+// Generalized puzzle over k stacks: `a` holds the values to sort and the
+// auxiliary stacks are named `b`, `c` and so on. Instructions are written
+// `p<from><to>`, `s<stack>`, `r<stack>` and `rr<stack>`, so that `pab`
+// is the classic `pb` and `rrb` keeps its meaning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MultiInstruction {
+    Push(usize, usize),
+    Swap(usize),
+    Rotate(usize),
+    RRotate(usize),
+}
+
+// Stacks are named with a single letter
+pub const MAX_STACKS: usize = 26;
+
+impl MultiInstruction {
+    // Every instruction over `k` stacks
+    pub fn all(k: usize) -> Vec<MultiInstruction> {
+        use self::MultiInstruction::*;
+
+        let pushes = (0..k)
+            .flat_map(|from| (0..k).filter(move |to| *to != from).map(move |to| Push(from, to)));
+        let others = (0..k).flat_map(|stack| vec![Swap(stack), Rotate(stack), RRotate(stack)]);
+
+        pushes.chain(others).collect()
+    }
+
+    // The instruction undoing `self`. Only holds for pushes if the pushed
+    // from stack was not empty
+    pub fn inverse(&self) -> MultiInstruction {
+        use self::MultiInstruction::*;
+
+        match *self {
+            Push(from, to) => Push(to, from),
+            Swap(stack)    => Swap(stack),
+            Rotate(stack)  => RRotate(stack),
+            RRotate(stack) => Rotate(stack),
+        }
+    }
+
+    // Number of stacks needed to execute `self`
+    pub fn stacks(&self) -> usize {
+        use self::MultiInstruction::*;
+        use std::cmp::max;
+
+        match *self {
+            Push(from, to) => max(from, to) + 1,
+            Swap(stack) | Rotate(stack) | RRotate(stack) => stack + 1,
+        }
+    }
+}
+
+pub fn execute_multi<S: Stack<N>>(instr: &MultiInstruction, stacks: &mut [S]) {
+    use self::MultiInstruction::*;
+
+    match *instr {
+        Push(from, to) => {
+            if let Some(n) = stacks[from].pop() {
+                stacks[to].push(n)
+            }
+        },
+        Swap(stack)    => stacks[stack].swap(),
+        Rotate(stack)  => stacks[stack].rotate(),
+        RRotate(stack) => stacks[stack].rrotate(),
+    }
+}
+
+pub fn is_solved_multi<S: Stack<N>>(stacks: &[S]) -> bool {
+    stacks[0].is_sorted() && stacks[1..].iter().all(|stack| stack.is_empty())
+}
+
+fn stack_index(name: char) -> Option<usize> {
+    match name {
+        'a'..='z' => Some(name as usize - 'a' as usize),
+        _         => None,
+    }
+}
+
+fn stack_name(index: usize) -> char {
+    (b'a' + index as u8) as char
+}
+
+impl FromStr for MultiInstruction {
+    type Err = InvalidInstruction;
+
+    fn from_str(as_string: &str) -> Result<Self, Self::Err> {
+        use self::MultiInstruction::*;
+
+        let chars = as_string.chars().collect::<Vec<_>>();
+
+        let instr = match *chars.as_slice() {
+            ['p', from, to] if from != to => {
+                stack_index(from).and_then(|from| stack_index(to).map(|to| Push(from, to)))
+            },
+            ['s', stack]      => stack_index(stack).map(Swap),
+            ['r', stack]      => stack_index(stack).map(Rotate),
+            ['r', 'r', stack] => stack_index(stack).map(RRotate),
+            _                 => None,
+        };
+
+        instr.ok_or_else(|| InvalidInstruction(String::from(as_string)))
+    }
+}
+
+impl fmt::Display for MultiInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::MultiInstruction::*;
+
+        match *self {
+            Push(from, to) => write!(f, "p{}{}", stack_name(from), stack_name(to)),
+            Swap(stack)    => write!(f, "s{}", stack_name(stack)),
+            Rotate(stack)  => write!(f, "r{}", stack_name(stack)),
+            RRotate(stack) => write!(f, "rr{}", stack_name(stack)),
+        }
+    }
+}
@@ -7,12 +7,23 @@ pub struct Options {
     pub command: Command
 }
 
+// Commands taking a stack accept negative values as positionals
 #[derive(StructOpt, Debug)]
 pub enum Command {
-    #[structopt(name = "check")]
+    #[structopt(name = "check", raw(setting="AppSettings::AllowNegativeNumbers"))]
     Check(CheckConfig),
-    #[structopt(name = "solve")]
-    Solve(SolveConfig)
+    #[structopt(name = "solve", raw(setting="AppSettings::AllowNegativeNumbers"))]
+    Solve(SolveConfig),
+    #[structopt(name = "visualize", raw(setting="AppSettings::AllowNegativeNumbers"))]
+    Visualize(VisualizeConfig),
+    #[structopt(name = "render", raw(setting="AppSettings::AllowNegativeNumbers"))]
+    Render(RenderConfig),
+    #[structopt(name = "generate")]
+    Generate(GenerateConfig),
+    #[structopt(name = "bench")]
+    Bench(BenchConfig),
+    #[structopt(name = "grade")]
+    Grade(GradeConfig),
 }
 
 #[derive(StructOpt, Debug)]
@@ -20,56 +31,254 @@ pub struct CheckConfig {
     #[structopt(short="d", long="debug-states")]
     pub debug_states: bool,
 
+    #[structopt(long="strict")]
+    pub strict: bool,
+
+    #[structopt(long="step")]
+    pub step: bool,
+
+    #[structopt(long="format", default_value="human")]
+    pub format: OutputFormat,
+
+    // File of `<instruction> <cost>` lines, reported alongside the moves
+    #[structopt(long="costs", parse(try_from_os_str="read_costs"))]
+    pub costs: Option<Costs>,
+
+    // Comma separated instructions, any other one is rejected
+    #[structopt(long="allowed")]
+    pub allowed: Option<InstructionSet>,
+
+    // `ascending`, `descending` or a file listing the values of A, top first
+    #[structopt(long="target", default_value="ascending", parse(try_from_os_str="read_target"))]
+    pub target: Target,
+
+    // Generalized mode: instructions are read as `p<from><to>`, `s<stack>`,
+    // `r<stack>` and `rr<stack>` over A and this many auxiliary stacks
+    #[structopt(short="k", long="aux-stacks")]
+    pub aux_stacks: Option<usize>,
+
     // Positional
-    pub raw_stack: Vec<u32>,
+    pub raw_stack: Vec<i64>,
 }
 
 #[derive(StructOpt, Debug)]
 pub struct SolveConfig {
-    #[structopt(short="s", long="strategy", default_value="dumb")]
-    pub strategy: SolveStrategy,
+    #[structopt(short="s", long="strategy", raw(help="auto_strategy_help()"))]
+    pub strategy: Option<SolveStrategy>,
 
     #[structopt(short="p", long="par-threads")]
     pub par_threads: Option<usize>,
 
+    #[structopt(long="list-strategies")]
+    pub list_strategies: bool,
+
+    // File of `<instruction> <cost>` lines, the exact strategies minimize
+    // the total cost instead of the number of moves
+    #[structopt(long="costs", parse(try_from_os_str="read_costs"))]
+    pub costs: Option<Costs>,
+
+    // Comma separated instructions the solution may use
+    #[structopt(long="allowed")]
+    pub allowed: Option<InstructionSet>,
+
+    // `ascending`, `descending` or a file listing the values of A, top first
+    #[structopt(long="target", default_value="ascending", parse(try_from_os_str="read_target"))]
+    pub target: Target,
+
+    // Generalized mode: solves with this many auxiliary stacks, printing
+    // instructions as `p<from><to>`, `s<stack>`, `r<stack>` and `rr<stack>`
+    #[structopt(short="k", long="aux-stacks")]
+    pub aux_stacks: Option<usize>,
+
     // Positional
-    pub raw_stack: Vec<u32>
+    pub raw_stack: Vec<i64>
 }
 
-#[derive(Debug)]
-pub enum SolveStrategy {
-    AStar, ParAStar, NaiveInsert, SmartInsert,
+#[derive(StructOpt, Debug)]
+pub struct VisualizeConfig {
+    #[structopt(long="speed", default_value="10")]
+    pub speed: f32,
+
+    // Positional
+    pub raw_stack: Vec<i64>
+}
+
+#[derive(StructOpt, Debug)]
+pub struct RenderConfig {
+    #[structopt(short="o", long="output", parse(from_os_str))]
+    pub output: Option<PathBuf>,
+
+    #[structopt(short="f", long="format", default_value="html")]
+    pub format: RenderFormat,
+
+    // Duration of a single instruction in the animation
+    #[structopt(long="frame-ms", default_value="100")]
+    pub frame_ms: u32,
+
+    // Positional
+    pub raw_stack: Vec<i64>
+}
+
+#[derive(StructOpt, Debug)]
+pub struct GenerateConfig {
+    pub size: usize,
+
+    #[structopt(long="seed")]
+    pub seed: Option<u64>,
+
+    #[structopt(long="min", allow_hyphen_values=true)]
+    pub min: Option<i64>,
+
+    #[structopt(long="max", allow_hyphen_values=true)]
+    pub max: Option<i64>,
+
+    #[structopt(long="signed")]
+    pub signed: bool,
+
+    #[structopt(short="d", long="distribution", default_value="uniform")]
+    pub distribution: Distribution,
+
+    // Random transpositions applied by the nearly-sorted distribution
+    #[structopt(long="swaps", default_value="1")]
+    pub swaps: usize,
+
+    // Offset of the rotated distribution, random by default
+    #[structopt(long="rotation")]
+    pub rotation: Option<usize>,
+
+    #[structopt(short="o", long="output", parse(from_os_str))]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(StructOpt, Debug)]
+pub struct BenchConfig {
+    #[structopt(long="sizes", use_delimiter=true, default_value="3,5,100,500")]
+    pub sizes: Vec<usize>,
+
+    #[structopt(long="samples", default_value="100")]
+    pub samples: usize,
+
+    #[structopt(short="s", long="strategies", use_delimiter=true, default_value="smart,naive",
+                raw(help="strategy_help()"))]
+    pub strategies: Vec<SolveStrategy>,
+
+    #[structopt(short="p", long="par-threads")]
+    pub par_threads: Option<usize>,
+
+    #[structopt(long="seed")]
+    pub seed: Option<u64>,
+}
+
+#[derive(StructOpt, Debug)]
+pub struct GradeConfig {
+    #[structopt(short="s", long="strategy", default_value="smart", raw(help="strategy_help()"))]
+    pub strategy: SolveStrategy,
+
+    #[structopt(long="samples", default_value="100")]
+    pub samples: usize,
+
+    #[structopt(short="t", long="table", parse(from_os_str))]
+    pub table: Option<PathBuf>,
+
+    #[structopt(short="p", long="par-threads")]
+    pub par_threads: Option<usize>,
+
+    #[structopt(long="seed")]
+    pub seed: Option<u64>,
 }
 
 #[derive(Debug)]
 pub enum StackType {
-    LinkedList, VecDeque, Vec
+    LinkedList, VecDeque, Vec, Ring, Small
+}
+
+#[derive(Debug)]
+pub enum OutputFormat {
+    Human, Json
 }
 
+#[derive(Debug)]
+pub enum RenderFormat {
+    Html, Svg
+}
+
+use structopt::clap::AppSettings;
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::PathBuf;
 use std::str::FromStr;
 
-impl FromStr for SolveStrategy {
+use push_swap::solver::SolveStrategy;
+use push_swap::generator::Distribution;
+use push_swap::costs::Costs;
+use push_swap::instruction::InstructionSet;
+use push_swap::target::Target;
+
+// clap only takes static help strings, built once from the registry
+fn strategy_help() -> &'static str {
+    let help = format!("One of: {}", SolveStrategy::names().join(", "));
+    Box::leak(help.into_boxed_str())
+}
+
+fn auto_strategy_help() -> &'static str {
+    let help = format!("{} [default: picked from the stack size]", strategy_help());
+    Box::leak(help.into_boxed_str())
+}
+
+fn read_costs(path: &OsStr) -> Result<Costs, std::ffi::OsString> {
+    let source = fs::read_to_string(path)
+        .map_err(|err| format!("cannot read {}: {}", path.to_string_lossy(), err))?;
+
+    Ok(source.parse()?)
+}
+
+// Anything but the keywords is the path of a file holding the target order
+fn read_target(arg: &OsStr) -> Result<Target, std::ffi::OsString> {
+    if let Ok(target) = arg.to_string_lossy().parse() {
+        return Ok(target)
+    }
+
+    let source = fs::read_to_string(arg)
+        .map_err(|err| format!("cannot read {}: {}", arg.to_string_lossy(), err))?;
+
+    Ok(Target::from_order(&source)?)
+}
+
+impl FromStr for StackType {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
-            "astar"        | "a*"    => Ok(SolveStrategy::AStar),
-            "par-astar"    | "para*" => Ok(SolveStrategy::ParAStar),
-            "naive-insert" | "naive" => Ok(SolveStrategy::NaiveInsert),
-            "smart-insert" | "smart" => Ok(SolveStrategy::SmartInsert),
+            "linked-list" | "ll" => Ok(StackType::LinkedList),
+            "vec-deque"   | "vd" => Ok(StackType::VecDeque),
+            "vec"         | "v"  => Ok(StackType::Vec),
+            "ring"        | "r"  => Ok(StackType::Ring),
+            "small"       | "s"  => Ok(StackType::Small),
             invalid => Err(String::from(invalid))
         }
     }
 }
 
-impl FromStr for StackType {
+impl FromStr for OutputFormat {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
-            "linked-list" | "ll" => Ok(StackType::LinkedList),
-            "vec-deque"   | "vd" => Ok(StackType::VecDeque),
-            "vec"         | "v"  => Ok(StackType::Vec),
+            "human" => Ok(OutputFormat::Human),
+            "json"  => Ok(OutputFormat::Json),
+            invalid => Err(String::from(invalid))
+        }
+    }
+}
+
+impl FromStr for RenderFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "html"  => Ok(RenderFormat::Html),
+            "svg"   => Ok(RenderFormat::Svg),
             invalid => Err(String::from(invalid))
         }
     }
@@ -0,0 +1,129 @@
+use push_swap::utils::{N, to_vec};
+use push_swap::stack::Stack;
+
+use super::Animation;
+
+// The page replays the instructions itself so that its size only grows
+// with the input rather than with instructions × values
+const TEMPLATE: &str = r##"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>push-swap replay</title>
+<style>
+  body { background: #1e1e1e; color: #ccc; font-family: monospace; margin: 1em; }
+  button, input { font-family: monospace; }
+  canvas { display: block; margin-top: 1em; }
+</style>
+</head>
+<body>
+<div>
+  <button id="play">play</button>
+  <button id="back">&lt;</button>
+  <button id="next">&gt;</button>
+  <input id="seek" type="range" min="0" value="0" style="width: 40em">
+  <span id="status"></span>
+</div>
+<canvas id="stacks" width="800" height="630"></canvas>
+<script>
+const initial = {{INITIAL}};
+const instructions = {{INSTRUCTIONS}};
+const frameMs = {{FRAME_MS}};
+const [min, max] = {{VALUE_RANGE}};
+
+const canvas = document.getElementById("stacks");
+const ctx = canvas.getContext("2d");
+const seek = document.getElementById("seek");
+const statusLine = document.getElementById("status");
+const playButton = document.getElementById("play");
+seek.max = instructions.length;
+
+let a, b, cursor, timer = null;
+
+function reset() { a = initial.slice(); b = []; cursor = 0; }
+
+function swap(s) { if (s.length >= 2) { [s[0], s[1]] = [s[1], s[0]]; } }
+function rotate(s) { if (s.length) { s.push(s.shift()); } }
+function rrotate(s) { if (s.length) { s.unshift(s.pop()); } }
+function push(from, to) { if (from.length) { to.unshift(from.shift()); } }
+
+function execute(instr) {
+  switch (instr) {
+    case "pa": push(b, a); break;
+    case "pb": push(a, b); break;
+    case "sa": swap(a); break;
+    case "sb": swap(b); break;
+    case "ss": swap(a); swap(b); break;
+    case "ra": rotate(a); break;
+    case "rb": rotate(b); break;
+    case "rr": rotate(a); rotate(b); break;
+    case "rra": rrotate(a); break;
+    case "rrb": rrotate(b); break;
+    case "rrr": rrotate(a); rrotate(b); break;
+  }
+}
+
+function goTo(target) {
+  if (target < cursor) { reset(); }
+  while (cursor < target && cursor < instructions.length) { execute(instructions[cursor++]); }
+  draw();
+}
+
+function draw() {
+  const columnWidth = (canvas.width - 20) / 2;
+  const barHeight = Math.max(1, (canvas.height - 30) / Math.max(1, initial.length));
+  ctx.clearRect(0, 0, canvas.width, canvas.height);
+  ctx.fillStyle = "#ccc";
+  ctx.font = "16px monospace";
+  ctx.fillText("A", 0, 20);
+  ctx.fillText("B", columnWidth + 20, 20);
+  [[a, 0], [b, columnWidth + 20]].forEach(([stack, x]) => {
+    stack.forEach((value, row) => {
+      const ratio = max > min ? (value - min) / (max - min) : 1;
+      ctx.fillStyle = `hsl(${240 - 240 * ratio}, 80%, 55%)`;
+      ctx.fillRect(x, 30 + row * barHeight, 4 + ratio * (columnWidth - 4), barHeight);
+    });
+  });
+  seek.value = cursor;
+  const last = cursor > 0 ? instructions[cursor - 1] : "-";
+  statusLine.textContent = `step ${cursor}/${instructions.length} (last: ${last})`;
+}
+
+function pause() { clearInterval(timer); timer = null; playButton.textContent = "play"; }
+
+function play() {
+  if (cursor === instructions.length) { reset(); }
+  playButton.textContent = "pause";
+  timer = setInterval(() => {
+    if (cursor >= instructions.length) { pause(); return; }
+    goTo(cursor + 1);
+  }, frameMs);
+}
+
+playButton.onclick = () => timer === null ? play() : pause();
+document.getElementById("back").onclick = () => { pause(); goTo(Math.max(0, cursor - 1)); };
+document.getElementById("next").onclick = () => { pause(); goTo(cursor + 1); };
+seek.oninput = () => { pause(); goTo(Number(seek.value)); };
+
+reset();
+draw();
+</script>
+</body>
+</html>
+"##;
+
+pub fn document<S: Stack<N>>(animation: &Animation<S>) -> String {
+    let initial = to_vec(&animation.initial).iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>();
+    let instructions = animation.instructions.iter()
+        .map(|instr| format!("\"{}\"", instr))
+        .collect::<Vec<_>>();
+    let (min, max) = animation.value_range;
+
+    TEMPLATE
+        .replace("{{INITIAL}}", &format!("[{}]", initial.join(",")))
+        .replace("{{INSTRUCTIONS}}", &format!("[{}]", instructions.join(",")))
+        .replace("{{FRAME_MS}}", &animation.frame_ms.to_string())
+        .replace("{{VALUE_RANGE}}", &format!("[{}, {}]", min, max))
+}
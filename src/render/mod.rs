@@ -0,0 +1,55 @@
+use std::fs::File;
+use std::io::{self, Write};
+
+use push_swap::utils::N;
+use push_swap::stack::Stack;
+use push_swap::instruction::Instruction;
+use checker::read_instructions;
+
+use options::{RenderConfig, RenderFormat};
+
+mod html;
+mod svg;
+
+pub struct Animation<S> {
+    pub initial: S,
+    pub instructions: Vec<Instruction>,
+    pub value_range: (N, N),
+    pub frame_ms: u32,
+}
+
+pub fn render<S: Stack<N>>(config: RenderConfig) {
+    let RenderConfig { output, format, frame_ms, raw_stack } = config;
+
+    let animation = Animation {
+        value_range: (
+            raw_stack.iter().cloned().min().unwrap_or(0),
+            raw_stack.iter().cloned().max().unwrap_or(0)
+        ),
+        initial: raw_stack.into_iter().collect::<S>(),
+        instructions: read_instructions(io::stdin(), false)
+            .map(|instr| instr.expect("invalid instruction"))
+            .collect(),
+        frame_ms,
+    };
+
+    let document = match format {
+        RenderFormat::Html => html::document(&animation),
+        RenderFormat::Svg  => svg::document(&animation),
+    };
+
+    match output {
+        Some(path) => File::create(path)
+            .and_then(|mut file| file.write_all(document.as_bytes()))
+            .expect("Failed to write the output file"),
+        None => print!("{}", document),
+    }
+}
+
+// Hue going from blue (small values) to red (large values)
+pub fn hue(value: N, (min, max): (N, N)) -> u32 {
+    match max > min {
+        true  => 240 - (240 * value.abs_diff(min) as u128 / max.abs_diff(min) as u128) as u32,
+        false => 0,
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use push_swap::utils::{N, to_vec};
+use push_swap::stack::Stack;
+use push_swap::replay::Replay;
+
+use super::{Animation, hue};
+
+const COLUMN_WIDTH: usize = 390;
+const COLUMN_GAP: usize = 20;
+const HEADER_HEIGHT: usize = 30;
+const BARS_HEIGHT: usize = 600;
+// Frames held on the final state before the animation loops
+const END_PAUSE_FRAMES: usize = 10;
+
+type Position = (usize, usize);
+
+// Every bar is animated with discrete keyframes on its coordinates, which
+// keeps the document static and viewable anywhere but grows with
+// instructions × values: better suited to small stacks
+pub fn document<S: Stack<N>>(animation: &Animation<S>) -> String {
+    let value_count = animation.initial.len();
+    let bar_height = ::std::cmp::max(1, BARS_HEIGHT / ::std::cmp::max(1, value_count));
+
+    let width = 2 * COLUMN_WIDTH + COLUMN_GAP;
+    let height = HEADER_HEIGHT + value_count * bar_height;
+
+    let frame_count = animation.instructions.len() + 1;
+    let duration = (frame_count + END_PAUSE_FRAMES) as u32 * animation.frame_ms;
+
+    let tracks = track_positions(animation, bar_height);
+
+    let mut svg = String::new();
+
+    writeln!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+                   font-family=\"monospace\">", width, height).unwrap();
+    writeln!(svg, "<rect width=\"100%\" height=\"100%\" fill=\"#1e1e1e\"/>").unwrap();
+    writeln!(svg, "<text x=\"0\" y=\"20\" fill=\"#ccc\">A</text>").unwrap();
+    writeln!(svg, "<text x=\"{}\" y=\"20\" fill=\"#ccc\">B</text>",
+        COLUMN_WIDTH + COLUMN_GAP).unwrap();
+
+    for (value, track) in &tracks {
+        let (x, y) = track[0].1;
+
+        writeln!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"hsl({}, 80%, 55%)\">",
+            x, y, bar_width(*value, animation.value_range), bar_height,
+            hue(*value, animation.value_range)).unwrap();
+
+        if track.len() > 1 {
+            write_keyframes(&mut svg, "x", track, |(x, _)| x, frame_count, duration);
+            write_keyframes(&mut svg, "y", track, |(_, y)| y, frame_count, duration);
+        }
+
+        writeln!(svg, "</rect>").unwrap();
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// Positions taken by each value over time, as (frame, position) pairs only
+// recorded when the position changes
+fn track_positions<S: Stack<N>>(animation: &Animation<S>, bar_height: usize)
+    -> BTreeMap<N, Vec<(usize, Position)>>
+{
+    let mut tracks = BTreeMap::<N, Vec<(usize, Position)>>::new();
+    let mut replay = Replay::new(animation.initial.clone(), animation.instructions.clone());
+
+    for frame in 0..=replay.len() {
+        replay.seek(frame);
+
+        let columns = [(0, to_vec(replay.a())), (COLUMN_WIDTH + COLUMN_GAP, to_vec(replay.b()))];
+
+        for (x, values) in columns.iter() {
+            for (row, value) in values.iter().enumerate() {
+                let position = (*x, HEADER_HEIGHT + row * bar_height);
+                let track = tracks.entry(*value).or_default();
+
+                if track.last().map(|(_, last)| *last != position).unwrap_or(true) {
+                    track.push((frame, position));
+                }
+            }
+        }
+    }
+
+    tracks
+}
+
+fn write_keyframes<F>(svg: &mut String, attribute: &str, track: &[(usize, Position)],
+                      coordinate: F, frame_count: usize, duration: u32)
+where
+    F: Fn(Position) -> usize
+{
+    let values = track.iter()
+        .map(|(_, position)| coordinate(*position).to_string())
+        .collect::<Vec<_>>();
+    let key_times = track.iter()
+        .map(|(frame, _)| format!("{:.6}", *frame as f64 / (frame_count + END_PAUSE_FRAMES) as f64))
+        .collect::<Vec<_>>();
+
+    writeln!(svg, "  <animate attributeName=\"{}\" calcMode=\"discrete\" dur=\"{}ms\" \
+                   repeatCount=\"indefinite\" values=\"{}\" keyTimes=\"{}\"/>",
+        attribute, duration, values.join(";"), key_times.join(";")).unwrap();
+}
+
+fn bar_width(value: N, (min, max): (N, N)) -> usize {
+    match max > min {
+        true  => {
+            let offset = (COLUMN_WIDTH - 4) as u128 * value.abs_diff(min) as u128;
+            4 + (offset / max.abs_diff(min) as u128) as usize
+        },
+        false => COLUMN_WIDTH,
+    }
+}
@@ -0,0 +1,77 @@
+use utils::*;
+use stack::{Stack, History, Snapshot};
+use instruction::Instruction;
+
+// Random access over the states reached by an instruction stream
+pub struct Replay<S> {
+    instructions: Vec<Instruction>,
+    history: History<S>,
+}
+
+impl<S: Stack<N>> Replay<S> {
+    pub fn new(initial: S, instructions: Vec<Instruction>) -> Self {
+        let side_stack = initial.empty_like();
+
+        Replay {
+            instructions,
+            history: History::new(initial, side_stack),
+        }
+    }
+
+    pub fn a(&self) -> &S {
+        self.history.a()
+    }
+
+    pub fn b(&self) -> &S {
+        self.history.b()
+    }
+
+    pub fn snapshot(&self) -> Snapshot<S> {
+        self.history.snapshot()
+    }
+
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    // Number of instructions applied so far
+    pub fn cursor(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn last_instruction(&self) -> Option<&Instruction> {
+        self.history.last()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.cursor() == self.instructions.len()
+    }
+
+    // Moves to the state reached after `target` instructions
+    pub fn seek(&mut self, target: usize) {
+        use std::cmp::min;
+
+        let target = min(target, self.instructions.len());
+
+        while self.cursor() < target { self.forward() }
+        if self.cursor() > target {
+            let k = self.cursor() - target;
+            self.history.undo(k);
+        }
+    }
+
+    pub fn forward(&mut self) {
+        if let Some(instr) = self.instructions.get(self.cursor()) {
+            self.history.apply(instr)
+        }
+    }
+
+    // Undoes the last instruction by applying its inverse
+    pub fn backward(&mut self) {
+        self.history.undo(1);
+    }
+}
@@ -0,0 +1,129 @@
+use utils::*;
+use stack::Stack;
+use instruction::Instruction;
+use costs::Costs;
+
+pub struct Step<S> {
+    pub instr: Instruction,
+    pub a: S,
+    pub b: S,
+    // First step after which the remaining instructions are too few to
+    // possibly reach the solved state
+    pub dead_end: bool,
+}
+
+pub struct Report<S> {
+    pub initial: S,
+    pub a: S,
+    pub b: S,
+    pub moves: usize,
+    pub histogram: Vec<(Instruction, usize)>,
+    pub max_b_len: usize,
+    // Adjacent instruction pairs that cancel out or could be merged into a
+    // single instruction (counted without overlap)
+    pub wasted_pairs: usize,
+    pub fusable_pairs: usize,
+    pub trace: Vec<Step<S>>,
+    sorted: bool,
+    solved: bool,
+}
+
+impl<S: Stack<N>> Report<S> {
+    pub fn replay(initial: S, instructions: Vec<Instruction>, record_trace: bool)
+        -> Self
+    {
+        let ranked = initial.clone();
+        Report::replay_ranked(initial, ranked, instructions, record_trace)
+    }
+
+    // `ranked` holds the initial values mapped by `Target::ranks`, the
+    // instructions are replayed on it as well to tell when the target is
+    // reached
+    pub fn replay_ranked(initial: S, ranked: S, instructions: Vec<Instruction>,
+                         record_trace: bool)
+        -> Self
+    {
+        use std::cmp::max;
+
+        let mut a = initial.clone();
+        let mut b = initial.empty_like();
+        let (mut ranked_b, mut ranked_a) = (ranked.empty_like(), ranked);
+
+        let moves = instructions.len();
+        let mut counts = [0; 11];
+        let mut max_b_len = 0;
+        let (mut wasted_pairs, mut fusable_pairs) = (0, 0);
+        // Along with whether it changed the stacks: only then can the next
+        // instruction cancel it out
+        let mut prev_instr: Option<(Instruction, bool)> = None;
+        let mut trace = Vec::new();
+        let mut dead_end_flagged = false;
+
+        for (i, instr) in instructions.into_iter().enumerate() {
+            let lens = (a.len(), b.len());
+            execute(&instr, &mut (&mut a, &mut b));
+            let is_push = instr == Instruction::PushA || instr == Instruction::PushB;
+            let applied = !is_push || lens != (a.len(), b.len());
+            execute(&instr, &mut (&mut ranked_a, &mut ranked_b));
+
+            if let Some(idx) = Instruction::ALL.iter().position(|i| i == &instr) {
+                counts[idx] += 1;
+            }
+
+            max_b_len = max(max_b_len, b.len());
+
+            // A pair is consumed once counted so that `ra rra ra` only
+            // accounts for a single wasted pair
+            prev_instr = match prev_instr {
+                Some((ref prev, true)) if prev.cancels(&instr) => {
+                    wasted_pairs += 1;
+                    None
+                },
+                Some((ref prev, _)) if prev.fuse(&instr).is_some() => {
+                    fusable_pairs += 1;
+                    None
+                },
+                _ => Some((instr.clone(), applied))
+            };
+
+            if record_trace {
+                let moves_left = moves - (i + 1);
+                let dead_end = !dead_end_flagged
+                    && min_moves_left(&ranked_a, &ranked_b) > moves_left;
+                dead_end_flagged |= dead_end;
+
+                trace.push(Step { instr, a: a.clone(), b: b.clone(), dead_end });
+            }
+        }
+
+        let histogram = Instruction::ALL.iter().cloned()
+            .zip(counts.iter().cloned())
+            .collect();
+
+        Report {
+            initial, a, b, moves, histogram,
+            max_b_len, wasted_pairs, fusable_pairs,
+            trace, sorted: ranked_a.is_sorted(), solved: is_solved(&ranked_a, &ranked_b)
+        }
+    }
+
+    // Whether A ends up in the target order, regardless of B
+    pub fn is_sorted(&self) -> bool {
+        self.sorted
+    }
+
+    // Whether the target of the replay was reached
+    pub fn is_solved(&self) -> bool {
+        self.solved
+    }
+
+    pub fn ratio(&self) -> f32 {
+        self.moves as f32 / (self.a.len() + self.b.len()) as f32
+    }
+
+    pub fn cost(&self, costs: &Costs) -> u64 {
+        self.histogram.iter()
+            .map(|(instr, count)| costs.of(instr) as u64 * *count as u64)
+            .sum()
+    }
+}
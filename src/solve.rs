@@ -0,0 +1,86 @@
+use push_swap::utils::N;
+use push_swap::stack::Stack;
+use push_swap::solver::{solution, multi_solution, SolveStrategy, Rules, STRATEGIES};
+use push_swap::multi::MAX_STACKS;
+
+use options::SolveConfig;
+
+use std::fmt;
+use std::process::exit;
+
+pub fn solve<S: Stack<N>>(config: SolveConfig) {
+    let SolveConfig {
+        strategy, par_threads, list_strategies, costs, allowed, target, aux_stacks, raw_stack
+    } = config;
+
+    if list_strategies {
+        return print_strategies()
+    }
+
+    let strategy = strategy.unwrap_or_else(|| SolveStrategy::for_size(raw_stack.len()));
+
+    // Sorting the ranks in ascending order reaches the target
+    let stack = match target.ranks(&raw_stack) {
+        Ok(ranks) => ranks.into_iter().collect::<S>(),
+        Err(err)  => fail(&err)
+    };
+
+    if let Some(aux_stacks) = aux_stacks {
+        if aux_stacks == 0 || aux_stacks >= MAX_STACKS {
+            fail(&format!("Between 1 and {} auxiliary stacks are supported", MAX_STACKS - 1))
+        }
+        if costs.is_some() || allowed.is_some() {
+            fail("--costs and --allowed only apply to the two stacks puzzle")
+        }
+
+        let instructions = multi_solution(&strategy, aux_stacks + 1, stack);
+        return print_solution(instructions.into_iter())
+    }
+
+    let rules = Rules { costs: costs.unwrap_or_default(), allowed: allowed.unwrap_or_default() };
+
+    match solution(&strategy, par_threads, &rules, stack) {
+        Ok(instructions) => print_solution(instructions),
+        Err(err)         => fail(&err)
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    exit(1)
+}
+
+fn print_strategies() {
+    println!("{:<14} {:<8} {:<8} {:<10} {:<11}",
+        "strategy", "aliases", "optimal", "sizes", "description");
+
+    for info in STRATEGIES.iter() {
+        let sizes = match info.sizes {
+            (min, Some(max)) => format!("{}-{}", min, max),
+            (min, None)      => format!("{}+", min),
+        };
+
+        println!("{:<14} {:<8} {:<8} {:<10} {}",
+            info.name, info.aliases.join(","), if info.optimal { "yes" } else { "no" },
+            sizes, info.description);
+    }
+}
+
+fn print_solution<T: fmt::Display>(solution: impl Iterator<Item = T>) {
+    use std::fmt::Write;
+
+    let buffer_size = 4096;
+    let output_buffer = String::with_capacity(buffer_size);
+
+    let remaining_output = solution
+        .fold(output_buffer, |mut buff, instr| {
+            if buff.len() >= buffer_size - 16 {
+                print!("{}", buff);
+                buff.clear();
+            }
+            writeln!(&mut buff, "{}", instr).unwrap();
+            buff
+        });
+
+    print!("{}", remaining_output);
+}
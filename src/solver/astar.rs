@@ -1,26 +1,35 @@
 use super::*;
 use super::utils::*;
 
-use std::collections::{VecDeque, HashSet};
+use std::collections::{BinaryHeap, HashSet, vec_deque::IntoIter};
 
-pub fn astar(stack: impl Stack<N>) -> impl Iterator<Item = Instruction> {
-    let mut open_set = VecDeque::new();
+// Uniform cost search: nodes are expanded cheapest first, in the order they
+// were found among equally expensive ones
+pub fn astar(stack: impl Stack<N>, rules: Rules) -> Result<IntoIter<Instruction>, String> {
+    let mut open_set = BinaryHeap::new();
     let mut closed_set = HashSet::new();
+    let mut found = 0;
 
-    open_set.push_back(Node { a: stack, ..Default::default() });
+    open_set.push(Queued { node: Node::new(&stack), order: found });
 
-    while let Some(node) = open_set.pop_front() {
-        if node.b.len() == 0 && node.a.is_sorted() {
-            return node.instrs.into_iter()
+    while let Some(Queued { node, .. }) = open_set.pop() {
+        if node.is_solved() {
+            return Ok(node.instrs.into_iter())
         }
 
-        closed_set.insert(hash(&node));
+        // Cheaper paths to the same stacks have already been expanded
+        if !closed_set.insert(hash(&node)) {
+            continue
+        }
 
-        let valid_neighbors = neighbors(node)
+        let valid_neighbors = neighbors(node, rules)
             .filter(|n| !closed_set.contains(&hash(n)));
 
-        open_set.extend(valid_neighbors);
+        for node in valid_neighbors {
+            found += 1;
+            open_set.push(Queued { node, order: found })
+        }
     }
 
-    unreachable!("Stacks are always solvable")
+    Err(format!("no solution using only {}", rules.allowed))
 }
@@ -2,8 +2,12 @@ extern crate num_cpus;
 
 use utils::*;
 use stack::Stack;
-use instruction::Instruction;
-use options::{SolveConfig, SolveStrategy};
+use instruction::{Instruction, InstructionSet};
+use costs::Costs;
+use multi::MultiInstruction;
+
+use std::str::FromStr;
+use std::fmt;
 
 mod utils;
 
@@ -11,47 +15,174 @@ mod astar;
 mod par_astar;
 mod naive_insert;
 mod smart_insert;
+mod restrict;
+mod multi;
+
+#[cfg(test)]
+mod tests;
 
 use self::astar::astar;
 use self::naive_insert::naive_insert;
 use self::smart_insert::smart_insert;
 use self::par_astar::par_astar;
+use self::restrict::Restriction;
+use self::multi::{multi_search, multi_insert};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveStrategy {
+    AStar, ParAStar, NaiveInsert, SmartInsert,
+}
+
+// Constraints of the puzzle variant being solved
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rules {
+    pub costs: Costs,
+    pub allowed: InstructionSet,
+}
+
+pub struct StrategyInfo {
+    pub strategy: SolveStrategy,
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    pub description: &'static str,
+    // Whether solutions are guaranteed to be of minimal length
+    pub optimal: bool,
+    // Inclusive range of stack sizes the strategy is recommended for
+    pub sizes: (usize, Option<usize>),
+    // Instructions found in solutions before they are restricted
+    pub emits: &'static [Instruction],
+}
+
+// Ordered by preference: the automatic default is the first strategy
+// recommended for the size of the stack
+pub static STRATEGIES: [StrategyInfo; 4] = [
+    StrategyInfo {
+        strategy: SolveStrategy::AStar,
+        name: "astar",
+        aliases: &["a*"],
+        description: "Exhaustive search of a shortest solution",
+        optimal: true,
+        sizes: (0, Some(6)),
+        emits: &Instruction::ALL,
+    },
+    StrategyInfo {
+        strategy: SolveStrategy::ParAStar,
+        name: "par-astar",
+        aliases: &["para*"],
+        description: "Exhaustive search spread over worker threads",
+        optimal: true,
+        sizes: (0, Some(7)),
+        emits: &Instruction::ALL,
+    },
+    StrategyInfo {
+        strategy: SolveStrategy::SmartInsert,
+        name: "smart-insert",
+        aliases: &["smart"],
+        description: "Inserts values into a sorted B, picking the cheapest rotations",
+        optimal: false,
+        sizes: (0, None),
+        emits: &[
+            Instruction::PushA, Instruction::PushB,
+            Instruction::RotateA, Instruction::RotateB, Instruction::RotateBoth,
+            Instruction::RRotateA, Instruction::RRotateB, Instruction::RRotateBoth,
+        ],
+    },
+    StrategyInfo {
+        strategy: SolveStrategy::NaiveInsert,
+        name: "naive-insert",
+        aliases: &["naive"],
+        description: "Pushes the minimum to B until A is sorted, then pushes back",
+        optimal: false,
+        sizes: (0, Some(100)),
+        emits: &[Instruction::PushA, Instruction::PushB, Instruction::RotateA, Instruction::RRotateA],
+    },
+];
+
+impl StrategyInfo {
+    pub fn is_recommended_for(&self, size: usize) -> bool {
+        match self.sizes {
+            (min, Some(max)) => min <= size && size <= max,
+            (min, None)      => min <= size,
+        }
+    }
+}
+
+impl SolveStrategy {
+    pub fn info(&self) -> &'static StrategyInfo {
+        STRATEGIES.iter()
+            .find(|info| info.strategy == *self)
+            .expect("Strategy missing from the registry")
+    }
+
+    pub fn for_size(size: usize) -> SolveStrategy {
+        STRATEGIES.iter()
+            .find(|info| info.is_recommended_for(size))
+            .map(|info| info.strategy)
+            .unwrap_or(SolveStrategy::SmartInsert)
+    }
+
+    pub fn names() -> Vec<&'static str> {
+        STRATEGIES.iter().map(|info| info.name).collect()
+    }
+}
 
-pub fn solve<S: Stack<N>>(config: SolveConfig) {
-    let SolveConfig { strategy, par_threads, raw_stack } = config;
-    let stack = raw_stack.into_iter().collect::<S>();
+// Only the exact searches take the costs into account, insertion based
+// strategies always minimize the number of moves. They also emit any
+// instruction, which get spelled out with the allowed ones afterwards.
+// Fails when the allowed instructions cannot sort the stack
+pub fn solution<S: Stack<N>>(strategy: &SolveStrategy, par_threads: Option<usize>,
+                             rules: &Rules, stack: S)
+    -> Result<Box<dyn Iterator<Item = Instruction>>, String>
+{
+    let restriction = Restriction::new(&rules.allowed);
+    let len = stack.len();
+
+    // Heuristics whose instructions cannot be spelled out with the allowed
+    // ones hand over to the next heuristic of the registry that can
+    let strategy = STRATEGIES.iter()
+        .skip_while(|info| info.strategy != *strategy)
+        .find(|info| info.optimal || restriction.expresses(info.emits))
+        .map(|info| info.strategy)
+        .ok_or_else(|| format!("no heuristic can be restricted to {}", rules.allowed))?;
 
-    match strategy {
-        SolveStrategy::AStar       => solve_with(astar, stack),
-        SolveStrategy::NaiveInsert => solve_with(naive_insert, stack),
-        SolveStrategy::SmartInsert => solve_with(smart_insert, stack),
+    Ok(match strategy {
+        SolveStrategy::AStar       => Box::new(astar(stack, *rules)?),
+        SolveStrategy::NaiveInsert => Box::new(restriction.apply(naive_insert(stack), len)),
+        SolveStrategy::SmartInsert => Box::new(restriction.apply(smart_insert(stack), len)),
         SolveStrategy::ParAStar    => {
             let n_threads = par_threads.unwrap_or_else(num_cpus::get);
-            solve_with(par_astar(n_threads), stack)
+            Box::new(par_astar(n_threads, *rules)(stack)?)
         }
-    }
+    })
 }
 
-fn solve_with<S, Solver, Solution>(solver: Solver, stack: S)
-where
-    S: Stack<N>,
-    Solver: FnOnce(S) -> Solution,
-    Solution: Iterator<Item = Instruction>
+// Solutions over `k` stacks: exact strategies share a single breadth first
+// search and the heuristics a single insertion
+pub fn multi_solution<S: Stack<N>>(strategy: &SolveStrategy, k: usize, stack: S)
+    -> Vec<MultiInstruction>
 {
-    use std::fmt::Write;
-
-    let buffer_size = 4096;
-    let output_buffer = String::with_capacity(buffer_size);
-
-    let remaining_output = solver(stack)
-        .fold(output_buffer, |mut buff, instr| {
-            if buff.len() >= buffer_size - 16 {
-                print!("{}", buff);
-                buff.clear();
-            }
-            writeln!(&mut buff, "{}", instr).unwrap();
-            buff
-        });
-
-    print!("{}", remaining_output);
+    match strategy.info().optimal {
+        true  => multi_search(stack, k),
+        false => multi_insert(stack, k),
+    }
+}
+
+impl FromStr for SolveStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+
+        STRATEGIES.iter()
+            .find(|info| info.name == s || info.aliases.contains(&s.as_str()))
+            .map(|info| info.strategy)
+            .ok_or_else(|| format!("unknown strategy '{}', expected one of: {}",
+                                   s, SolveStrategy::names().join(", ")))
+    }
+}
+
+impl fmt::Display for SolveStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.info().name)
+    }
 }
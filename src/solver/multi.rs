@@ -0,0 +1,117 @@
+use super::*;
+use super::utils::hash;
+
+use multi::{MultiInstruction, execute_multi, is_solved_multi};
+
+use std::collections::{HashSet, VecDeque};
+use std::iter::repeat_n;
+
+// Breadth first search over `k` stacks, every instruction costs 1
+pub fn multi_search<S: Stack<N>>(stack: S, k: usize) -> Vec<MultiInstruction> {
+    let instrs = MultiInstruction::all(k);
+
+    let mut stacks = vec![stack.empty_like(); k];
+    stacks[0] = stack;
+
+    let mut open_set = VecDeque::new();
+    let mut closed_set = HashSet::new();
+
+    closed_set.insert(hash(&stacks));
+    open_set.push_back((stacks, Vec::new()));
+
+    while let Some((stacks, path)) = open_set.pop_front() {
+        if is_solved_multi(&stacks) {
+            return path
+        }
+
+        let last_instr = path.last().cloned();
+
+        for instr in instrs.iter().filter(|instr| is_useful(instr, &stacks, last_instr)) {
+            let mut next = stacks.clone();
+            execute_multi(instr, &mut next);
+
+            if closed_set.insert(hash(&next)) {
+                let mut next_path = path.clone();
+                next_path.push(*instr);
+                open_set.push_back((next, next_path));
+            }
+        }
+    }
+
+    unreachable!("Stacks are always solvable with an auxiliary stack")
+}
+
+fn is_useful<S: Stack<N>>(instr: &MultiInstruction, stacks: &[S],
+                          last_instr: Option<MultiInstruction>) -> bool
+{
+    use self::MultiInstruction::*;
+
+    if last_instr == Some(instr.inverse()) {
+        return false
+    }
+
+    match *instr {
+        // Empty auxiliary stacks are interchangeable, only the first one
+        // of them is ever pushed onto
+        Push(from, to) => {
+            let first_empty = (1..stacks.len()).find(|&i| stacks[i].is_empty());
+            !stacks[from].is_empty()
+                && (to == 0 || !stacks[to].is_empty() || first_empty == Some(to))
+        },
+        Swap(stack) => stacks[stack].len() >= 2,
+        // Rotating two values is the same as swapping them
+        Rotate(stack) | RRotate(stack) => stacks[stack].len() >= 3,
+    }
+}
+
+// Spreads values over the auxiliary stacks by rank, inserting each of them
+// so that every auxiliary stack stays sorted in descending order, then
+// pushes the stacks back onto `a` starting from the largest values
+pub fn multi_insert<S: Stack<N>>(stack: S, k: usize) -> Vec<MultiInstruction> {
+    use self::MultiInstruction::*;
+
+    let mut sorted = to_vec(&stack);
+    sorted.sort();
+    let bucket = |n: &N| {
+        let rank = sorted.binary_search(n).unwrap_or_else(|rank| rank);
+        1 + rank * (k - 1) / sorted.len()
+    };
+
+    let mut stacks = vec![stack.empty_like(); k];
+    stacks[0] = stack;
+    let mut out = Vec::new();
+
+    while let Some(&n) = stacks[0].get(0) {
+        let to = bucket(&n);
+
+        let at = stacks[to].insert_index(&n)
+            .or_else(|| stacks[to].maximum().map(|(_, idx)| idx))
+            .unwrap_or(0);
+        rotate_to(&mut stacks[to], to, at, &mut out);
+
+        execute_multi(&Push(0, to), &mut stacks);
+        out.push(Push(0, to));
+    }
+
+    for from in (1..k).rev() {
+        if let Some((_, at)) = stacks[from].maximum() {
+            rotate_to(&mut stacks[from], from, at, &mut out);
+        }
+
+        out.extend(repeat_n(Push(from, 0), stacks[from].len()));
+    }
+
+    out
+}
+
+// Brings the value at `at` on top, whichever way is the shortest
+fn rotate_to<S: Stack<N>>(stack: &mut S, index: usize, at: usize,
+                          out: &mut Vec<MultiInstruction>)
+{
+    match at <= stack.len() / 2 {
+        true  => out.extend(repeat_n(MultiInstruction::Rotate(index), at)),
+        false => out.extend(repeat_n(MultiInstruction::RRotate(index), stack.len() - at)),
+    }
+
+    stack.rotate_n(at);
+}
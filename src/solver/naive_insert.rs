@@ -1,39 +1,48 @@
 use super::*;
+use super::utils::*;
 
-macro_rules! yield_from {
-    ($x: expr) => { for e in $x { yield e } };
+use std::collections::VecDeque;
+use std::iter::repeat_n;
+
+pub fn naive_insert<S: Stack<N>>(stack: S) -> impl Iterator<Item = Instruction> {
+    StepIter::new(NaiveInsert { stack, pushed: 0 })
+}
+
+struct NaiveInsert<S> {
+    stack: S,
+    pushed: usize,
 }
 
-pub fn naive_insert(mut stack: impl Stack<N>)
-    -> impl Iterator<Item = Instruction>
-{
-    use self::Instruction::*;
+impl<S: Stack<N>> Steps for NaiveInsert<S> {
+    fn step(&mut self, out: &mut VecDeque<Instruction>) -> bool {
+        use self::Instruction::*;
 
-    IterGen(move || {
-        let mut pushed = 0;
+        let stack = &mut self.stack;
 
-        while let Some((_, min_idx)) = stack.minimum() {
+        if let Some((_, min_idx)) = stack.minimum() {
             // If the stack is sorted, relative to a pivot, we rotate to
             // said pivot and end
             if let Some(rot_idx) = stack.sorted_at() {
-                let (instr, n) = shortest_rotation(&stack, rot_idx);
-                yield_from!(repeat_n(instr, n));
-                break
-            }
-            // Otherwise we rotate to the next minimum value and push
-            let (instr, n) = shortest_rotation(&stack, min_idx);
+                let (instr, n) = shortest_rotation(stack, rot_idx);
+                out.extend(repeat_n(instr, n));
+            } else {
+                // Otherwise we rotate to the next minimum value and push
+                let (instr, n) = shortest_rotation(stack, min_idx);
 
-            stack.rotate_n(min_idx);
-            drop(stack.pop());
+                stack.rotate_n(min_idx);
+                stack.pop();
 
-            yield_from!(repeat_n(instr, n));
-            yield PushB;
+                out.extend(repeat_n(instr, n));
+                out.push_back(PushB);
 
-            pushed += 1;
+                self.pushed += 1;
+                return true
+            }
         }
 
-        for _ in 0..pushed { yield PushA }
-    })
+        out.extend(repeat_n(PushA, self.pushed));
+        false
+    }
 }
 
 fn shortest_rotation(stack: &impl Stack<N>, at: usize) -> (Instruction, usize) {
@@ -41,8 +50,9 @@ fn shortest_rotation(stack: &impl Stack<N>, at: usize) -> (Instruction, usize) {
 
     let mid = stack.len() / 2;
 
+    // Rotating a single value by its own index takes no move either way
     match at.cmp(&mid) {
         Less => (Instruction::RotateA, at),
-        _    => (Instruction::RRotateA, stack.len() - at),
+        _    => (Instruction::RRotateA, (stack.len() - at) % stack.len()),
     }
 }
@@ -6,34 +6,38 @@ use super::utils::*;
 
 use self::crossbeam_deque::{fifo as work_steal_fifo, Steal, Stealer};
 use std::sync::{mpsc, Arc, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 
-use std::collections::{VecDeque, HashSet, vec_deque::IntoIter};
+use std::collections::{BinaryHeap, VecDeque, HashSet, vec_deque::IntoIter};
 
-pub fn par_astar<S: Stack<N>>(n_threads: usize)
-    -> impl FnOnce(S) -> IntoIter<Instruction>
+pub fn par_astar<S: Stack<N>>(n_threads: usize, rules: Rules)
+    -> impl FnOnce(S) -> Result<IntoIter<Instruction>, String>
 {
     // We have the main worker thread + at least 1 stealer so the
     // amount of extra workers we can get is max(0, n_threads - 2)
     let extra_worker_count = n_threads.saturating_sub(2);
 
-    move |stack| solve(extra_worker_count, stack).into_iter()
+    move |stack| solve(extra_worker_count, rules, stack).map(VecDeque::into_iter)
 }
 
 type ClosedSet = Arc<RwLock<HashSet<u64>>>;
-// type Work<S> = smallvec::SmallVec<[(Node<S>, u64); 10]>;
-type Work<S> = Vec<(Node<S>, u64)>;
-type WorkSender<S> = mpsc::Sender<Work<S>>;
+type DoneFlag = Arc<AtomicBool>;
+type Work = Vec<Node>;
+type WorkSender = mpsc::Sender<Work>;
 
-fn solve(extra_worker_count: usize, stack: impl Stack<N>)
-    -> VecDeque<Instruction>
+fn solve(extra_worker_count: usize, rules: Rules, stack: impl Stack<N>)
+    -> Result<VecDeque<Instruction>, String>
 {
+    if stack.is_sorted() {
+        return Ok(VecDeque::new())
+    }
+
     let (open_set_worker, open_set_stealer) = work_steal_fifo();
     let (neighbors_tx, neighbors_rx) = mpsc::channel();
     let closed_set = ClosedSet::default();
-
-    open_set_worker.push(Node { a: stack, ..Default::default() });
-    let mut open_set_size = 1;
+    // Lets idle workers know they can stop once a solution has been found
+    let done = DoneFlag::default();
 
     // Spawning the work stealers (at least 1 + extras)
     for _ in 0..extra_worker_count {
@@ -41,63 +45,80 @@ fn solve(extra_worker_count: usize, stack: impl Stack<N>)
             let thread_set = closed_set.clone();
             let thread_stealer = open_set_stealer.clone();
             let thread_tx = neighbors_tx.clone();
-            move || compute_neighbors(thread_set, thread_stealer, thread_tx)
+            let thread_done = done.clone();
+            move || compute_neighbors(thread_set, thread_stealer, thread_tx, thread_done, rules)
         });
     }
 
     thread::spawn({
         let thread_set = closed_set.clone();
-        move || compute_neighbors(thread_set, open_set_stealer, neighbors_tx)
+        let thread_done = done.clone();
+        move || compute_neighbors(thread_set, open_set_stealer, neighbors_tx, thread_done, rules)
     });
 
-    // Process each batch of computed neighbors in the main thread
-    // Buffer them to prevent excessive write locking on the closed set
-    const NODE_BUFFER_SIZE: usize = 512;
+    let mut open_set = BinaryHeap::new();
+    let mut found = 0;
+    let mut best: Option<Node> = None;
 
-    let mut buff_nodes = Vec::with_capacity(NODE_BUFFER_SIZE);
-    let mut buff_hashes = Vec::with_capacity(NODE_BUFFER_SIZE);
+    open_set.push(Queued { node: Node::new(&stack), order: found });
 
-    while let Ok(nodes) = neighbors_rx.recv() {
-        open_set_size -= 1;
-
-        // Check for end condition and buffer nodes
-        for (node, hash) in nodes {
-            if node.b.len() == 0 && node.a.is_sorted() {
-                return node.instrs
-            }
-            buff_nodes.push(node);
-            buff_hashes.push(hash);
+    // Nodes are expanded by batches of equal cost: the cheapest solution
+    // found so far wins once nothing cheaper is left to expand
+    while let Some(frontier_cost) = open_set.peek().map(|queued| queued.node.cost) {
+        if best.as_ref().is_some_and(|best| best.cost <= frontier_cost) {
+            break
         }
 
-        // Process the buffer if there is no more work queued or if the buffer
-        // is about to spill:
-        // current len + (maximum neighbor count =~ 16) > buffer size
-        if open_set_size == 0 || buff_nodes.len() + 16 > NODE_BUFFER_SIZE {
-            closed_set.write().unwrap()
-                .extend(buff_hashes.drain(..));
+        let mut frontier = HashSet::new();
+        {
+            let closed = closed_set.read().unwrap();
 
-            open_set_size += buff_nodes.len();
+            while open_set.peek().is_some_and(|queued| queued.node.cost == frontier_cost) {
+                let node = open_set.pop().unwrap().node;
+                let node_hash = hash(&node);
 
-            buff_nodes.drain(..).for_each(|node| {
-                open_set_worker.push(node);
-            })
+                if !closed.contains(&node_hash) && frontier.insert(node_hash) {
+                    open_set_worker.push(node)
+                }
+            }
+        }
+        let in_flight = frontier.len();
+        closed_set.write().unwrap().extend(frontier);
+
+        // Every dispatched node comes back as a single batch of neighbors
+        for _ in 0..in_flight {
+            let nodes = neighbors_rx.recv()
+                .expect("Workers stopped before the search ended");
+
+            for node in nodes {
+                if node.is_solved() {
+                    if best.as_ref().is_none_or(|best| node.cost < best.cost) {
+                        best = Some(node)
+                    }
+                    continue
+                }
+
+                found += 1;
+                open_set.push(Queued { node, order: found })
+            }
         }
     }
 
-    unreachable!("Stacks are always solvable")
+    done.store(true, Ordering::Relaxed);
+
+    best.map(|best| best.instrs)
+        .ok_or_else(|| format!("no solution using only {}", rules.allowed))
 }
 
-fn compute_neighbors<S>(closed_set: ClosedSet, stealer: Stealer<Node<S>>, tx: WorkSender<S>)
-where
-    S: Stack<N>
+fn compute_neighbors(closed_set: ClosedSet, stealer: Stealer<Node>, tx: WorkSender,
+                     done: DoneFlag, rules: Rules)
 {
-    loop {
+    while !done.load(Ordering::Relaxed) {
         if let Steal::Data(node) = stealer.steal() {
-            let valid_neighbors = neighbors(node)
-                .map(|n| { let h = hash(&n); (n, h) })
-                .filter(|(_, h)| !closed_set.read().unwrap().contains(&h));
+            let valid_neighbors = neighbors(node, rules)
+                .filter(|n| !closed_set.read().unwrap().contains(&hash(n)));
 
-            if let Err(_) = tx.send(valid_neighbors.collect()) {
+            if tx.send(valid_neighbors.collect()).is_err() {
                 return
             }
         }
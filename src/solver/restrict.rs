@@ -0,0 +1,142 @@
+use super::*;
+
+use instruction::InstructionSet;
+use stack::Side;
+
+#[derive(Debug, Clone, Copy)]
+enum Times {
+    Once,
+    // Rotating a stack the other way round, one step short of a full turn
+    AllButOne(Side),
+}
+
+type Spelling = &'static [(Instruction, Times)];
+
+// Equivalent sequences for every instruction, by order of preference
+fn spellings(instr: &Instruction) -> &'static [Spelling] {
+    use self::Instruction::*;
+    use self::Times::*;
+
+    match instr {
+        PushA | PushB => &[],
+        SwapA         => &[&[(SwapBoth, Once), (SwapB, Once)],
+                           &[(PushB, Once), (RotateA, Once), (PushA, Once), (RRotateA, Once)]],
+        SwapB         => &[&[(SwapBoth, Once), (SwapA, Once)],
+                           &[(PushA, Once), (RotateB, Once), (PushB, Once), (RRotateB, Once)]],
+        SwapBoth      => &[&[(SwapA, Once), (SwapB, Once)]],
+        RotateA       => &[&[(RotateBoth, Once), (RRotateB, Once)],
+                           &[(RRotateA, AllButOne(Side::A))]],
+        RotateB       => &[&[(RotateBoth, Once), (RRotateA, Once)],
+                           &[(RRotateB, AllButOne(Side::B))]],
+        RotateBoth    => &[&[(RotateA, Once), (RotateB, Once)]],
+        RRotateA      => &[&[(RRotateBoth, Once), (RotateB, Once)],
+                           &[(RotateA, AllButOne(Side::A))]],
+        RRotateB      => &[&[(RRotateBoth, Once), (RotateA, Once)],
+                           &[(RotateB, AllButOne(Side::B))]],
+        RRotateBoth   => &[&[(RRotateA, Once), (RRotateB, Once)]],
+    }
+}
+
+// Rewrites solutions so that they only use allowed instructions, spelling
+// out the other ones with equivalent sequences
+#[derive(Debug, Clone, Copy)]
+pub struct Restriction {
+    allowed: InstructionSet,
+    plans: [Option<Spelling>; 11],
+}
+
+impl Restriction {
+    pub fn new(allowed: &InstructionSet) -> Self {
+        let mut plans: [Option<Spelling>; 11] = [None; 11];
+
+        // A spelling may only rely on instructions that were already
+        // expressible, which keeps the rewriting from looping
+        loop {
+            let mut progress = false;
+
+            for instr in Instruction::ALL.iter() {
+                let idx = instr.clone() as usize;
+                if allowed.contains(instr) || plans[idx].is_some() {
+                    continue
+                }
+
+                let expressible = |step: &Instruction| {
+                    allowed.contains(step) || plans[step.clone() as usize].is_some()
+                };
+
+                let plan = spellings(instr).iter().cloned()
+                    .find(|spelling| spelling.iter().all(|(step, _)| expressible(step)));
+                plans[idx] = plan;
+                progress |= plans[idx].is_some();
+            }
+
+            if !progress {
+                break
+            }
+        }
+
+        Restriction { allowed: *allowed, plans }
+    }
+
+    // Whether the instructions can all be spelled out with the allowed ones
+    pub fn expresses(&self, instrs: &[Instruction]) -> bool {
+        instrs.iter().all(|instr| {
+            self.allowed.contains(instr) || self.plans[instr.clone() as usize].is_some()
+        })
+    }
+
+    pub fn apply(self, solution: impl Iterator<Item = Instruction>, a_len: usize)
+        -> impl Iterator<Item = Instruction>
+    {
+        let mut lens = (a_len, 0);
+
+        solution.flat_map(move |instr| {
+            let mut out = Vec::new();
+            self.spell(&instr, &mut lens, &mut out);
+            out
+        })
+    }
+
+    fn spell(&self, instr: &Instruction, lens: &mut (usize, usize), out: &mut Vec<Instruction>) {
+        // Spellings are only equivalent to instructions that have an effect
+        if is_noop(instr, *lens) {
+            return
+        }
+
+        if self.allowed.contains(instr) {
+            *lens = match instr {
+                Instruction::PushA => (lens.0 + 1, lens.1 - 1),
+                Instruction::PushB => (lens.0 - 1, lens.1 + 1),
+                _                  => *lens,
+            };
+            return out.push(instr.clone())
+        }
+
+        let spelling = self.plans[instr.clone() as usize]
+            .unwrap_or_else(|| panic!("'{}' cannot be expressed with {}", instr, self.allowed));
+
+        for (step, times) in spelling {
+            let n = match times {
+                Times::Once               => 1,
+                Times::AllButOne(Side::A) => lens.0 - 1,
+                Times::AllButOne(Side::B) => lens.1 - 1,
+            };
+
+            for _ in 0..n {
+                self.spell(step, lens, out)
+            }
+        }
+    }
+}
+
+fn is_noop(instr: &Instruction, (a_len, b_len): (usize, usize)) -> bool {
+    use self::Instruction::*;
+
+    match instr {
+        PushA                               => b_len == 0,
+        PushB                               => a_len == 0,
+        SwapA | RotateA | RRotateA          => a_len < 2,
+        SwapB | RotateB | RRotateB          => b_len < 2,
+        SwapBoth | RotateBoth | RRotateBoth => a_len < 2 && b_len < 2,
+    }
+}
@@ -0,0 +1,349 @@
+use super::*;
+
+use stack::{LLStack, VDStack, VecStack, RingStack, SmallStack};
+use report::Report;
+use target::Target;
+use multi::{MultiInstruction, execute_multi, is_solved_multi};
+
+// Every strategy listed for a stack must solve it in at most the recorded
+// amount of moves: lower the bounds when a solver improves
+const CORPUS: &str = include_str!("corpus.txt");
+
+struct Case {
+    bounds: Vec<(SolveStrategy, usize)>,
+    stack: Vec<N>,
+}
+
+fn corpus() -> Vec<Case> {
+    CORPUS.lines()
+        .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+        .map(|line| {
+            let mut parts = line.split('|');
+            let (bounds, stack) = (parts.next().unwrap(), parts.next().unwrap());
+
+            let bounds = bounds.split_whitespace()
+                .map(|bound| {
+                    let mut parts = bound.split('=');
+                    let strategy = parts.next().unwrap().parse().unwrap();
+                    let max_moves = parts.next().unwrap().parse().unwrap();
+                    (strategy, max_moves)
+                })
+                .collect();
+
+            let stack = stack.split_whitespace()
+                .map(|n| n.parse().unwrap())
+                .collect();
+
+            Case { bounds, stack }
+        })
+        .collect()
+}
+
+fn check_corpus<S: Stack<N>>() {
+    for case in corpus() {
+        for (strategy, max_moves) in &case.bounds {
+            let stack = case.stack.iter().cloned().collect::<S>();
+            let instructions = solution(strategy, Some(2), &Rules::default(), stack.clone()).unwrap()
+                .collect::<Vec<_>>();
+            let moves = instructions.len();
+
+            assert!(Report::replay(stack, instructions, false).is_solved(),
+                "{} does not sort {:?}", strategy, case.stack);
+            assert!(moves <= *max_moves,
+                "{} regressed on {:?}: {} moves, at most {} expected",
+                strategy, case.stack, moves, max_moves);
+        }
+    }
+}
+
+#[test]
+fn corpus_linked_list() {
+    check_corpus::<LLStack<N>>()
+}
+
+#[test]
+fn corpus_vec_deque() {
+    check_corpus::<VDStack<N>>()
+}
+
+#[test]
+fn corpus_vec() {
+    check_corpus::<VecStack<N>>()
+}
+
+#[test]
+fn corpus_ring() {
+    check_corpus::<RingStack<N>>()
+}
+
+#[test]
+fn corpus_small() {
+    check_corpus::<SmallStack<N>>()
+}
+
+// Cost of a cheapest solution, from a plain Dijkstra over every state
+// reachable with the allowed instructions
+fn cheapest_cost(values: &[N], rules: &Rules) -> Option<u64> {
+    use std::collections::{BinaryHeap, HashSet};
+    use std::cmp::Reverse;
+    use utils::is_solved;
+
+    let a = values.iter().cloned().collect::<VDStack<N>>();
+    let mut open_set = BinaryHeap::new();
+    let mut closed_set = HashSet::new();
+    open_set.push(Reverse((0, to_vec(&a), Vec::new())));
+
+    while let Some(Reverse((cost, a, b))) = open_set.pop() {
+        let (a, b) = (a.into_iter().collect::<VDStack<N>>(), b.into_iter().collect::<VDStack<N>>());
+        if !closed_set.insert((to_vec(&a), to_vec(&b))) {
+            continue
+        }
+        if is_solved(&a, &b) {
+            return Some(cost)
+        }
+
+        for instr in Instruction::ALL.iter().filter(|instr| rules.allowed.contains(instr)) {
+            let (mut a, mut b) = (a.clone(), b.clone());
+            execute(instr, &mut (&mut a, &mut b));
+            open_set.push(Reverse((cost + rules.costs.of(instr) as u64, to_vec(&a), to_vec(&b))));
+        }
+    }
+
+    None
+}
+
+fn permutations(size: usize) -> Vec<Vec<N>> {
+    match size {
+        0 => vec![Vec::new()],
+        _ => permutations(size - 1).into_iter()
+            .flat_map(|perm| (0..size).map(move |at| {
+                let mut perm = perm.clone();
+                perm.insert(at, size as N - 1);
+                perm
+            }))
+            .collect(),
+    }
+}
+
+#[test]
+fn exact_strategies_minimize_cost() {
+    let profiles = [
+        "sa 10\nsb 10\nss 4\nrra 3",
+        "ra 5\nrb 5\nrr 1\npb 2",
+        "pa 4\npb 4\nrra 1\nrrb 1\nrrr 1\nss 3",
+    ];
+    let mut stacks = (1..5).flat_map(permutations).collect::<Vec<_>>();
+    stacks.push(vec![1, 0, 3, 2, 4]);
+
+    for profile in &profiles {
+        let costs = profile.parse::<Costs>().unwrap();
+        let rules = Rules { costs, ..Default::default() };
+
+        for values in &stacks {
+            let stack = values.iter().cloned().collect::<VDStack<N>>();
+            let unit = solution(&SolveStrategy::AStar, None, &Rules::default(), stack.clone()).unwrap()
+                .collect::<Vec<_>>();
+            let expected = cheapest_cost(values, &rules).unwrap();
+
+            for strategy in &[SolveStrategy::AStar, SolveStrategy::ParAStar] {
+                let instructions = solution(strategy, Some(2), &rules, stack.clone()).unwrap()
+                    .collect::<Vec<_>>();
+                assert!(Report::replay(stack.clone(), instructions.clone(), false).is_solved(),
+                    "{} does not sort {:?} under custom costs", strategy, values);
+                assert_eq!(costs.total(&instructions), expected,
+                    "{} misses the cheapest solution of {:?} with costs {:?}", strategy, values, profile);
+            }
+
+            assert!(expected <= costs.total(&unit));
+        }
+    }
+}
+
+#[test]
+fn restricted_searches_stay_exact() {
+    let variants = ["pa,pb,ss,rr,rrr", "pa,pb,sa,rr,rrr", "sa,ra", "pa,pb,sb,rra,rb", "pa,pb,ra", "pa,pb"];
+    let stacks = (1..5).flat_map(permutations).collect::<Vec<_>>();
+
+    for variant in &variants {
+        let rules = Rules { allowed: variant.parse().unwrap(), ..Default::default() };
+
+        for values in &stacks {
+            let stack = values.iter().cloned().collect::<VDStack<N>>();
+            let expected = cheapest_cost(values, &rules);
+
+            for strategy in &[SolveStrategy::AStar, SolveStrategy::ParAStar] {
+                let moves = solution(strategy, Some(2), &rules, stack.clone())
+                    .map(|instructions| instructions.count() as u64)
+                    .ok();
+                assert_eq!(moves, expected, "{} on {:?} with {}", strategy, values, variant);
+            }
+        }
+    }
+}
+
+#[test]
+fn solutions_only_use_allowed_instructions() {
+    let variants = ["sa,pb,pa,ra,rra", "pa,pb,rra,rrb,sa", "pa,pb,ra,rb,ss,sb", "sa,ra"];
+    let stacks: &[&[N]] = &[&[1, 0, 2], &[3, 1, 0, 2], &[1, 0, 3, 2, 4]];
+
+    for variant in &variants {
+        let rules = Rules { allowed: variant.parse().unwrap(), ..Default::default() };
+        let heuristics = Restriction::new(&rules.allowed)
+            .expresses(SolveStrategy::NaiveInsert.info().emits);
+
+        for values in stacks {
+            let stack = values.iter().cloned().collect::<VDStack<N>>();
+
+            for info in STRATEGIES.iter().filter(|info| info.optimal || heuristics) {
+                let instructions = solution(&info.strategy, Some(2), &rules, stack.clone()).unwrap()
+                    .collect::<Vec<_>>();
+
+                assert!(instructions.iter().all(|instr| rules.allowed.contains(instr)),
+                    "{} steps out of {} on {:?}", info.strategy, variant, values);
+                assert!(Report::replay(stack.clone(), instructions, false).is_solved(),
+                    "{} does not sort {:?} with {}", info.strategy, values, variant);
+            }
+        }
+    }
+
+    let large = (0..100).rev().collect::<VDStack<N>>();
+    let rules = Rules { allowed: "pa,pb,rra,rrb,sa".parse().unwrap(), ..Default::default() };
+    let instructions = solution(&SolveStrategy::SmartInsert, None, &rules, large.clone()).unwrap()
+        .collect::<Vec<_>>();
+
+    assert!(instructions.iter().all(|instr| rules.allowed.contains(instr)));
+    assert!(Report::replay(large, instructions, false).is_solved());
+}
+
+#[test]
+fn ranks_lead_solvers_to_any_target() {
+    let values: &[N] = &[4, 9, 1, 7, 3];
+    let targets = [
+        (Target::Descending, vec![9, 7, 4, 3, 1]),
+        (Target::from_order("7 1 9 3 4").unwrap(), vec![7, 1, 9, 3, 4]),
+    ];
+
+    for (target, expected) in &targets {
+        let stack = values.iter().cloned().collect::<VDStack<N>>();
+        let ranked = target.ranks(values).unwrap().into_iter().collect::<VDStack<N>>();
+
+        for info in STRATEGIES.iter() {
+            let instructions = solution(&info.strategy, Some(2), &Rules::default(), ranked.clone())
+                .unwrap()
+                .collect::<Vec<_>>();
+            let report = Report::replay_ranked(stack.clone(), ranked.clone(), instructions, false);
+
+            assert!(report.is_solved(), "{} misses {:?}", info.strategy, target);
+            assert!(report.is_sorted());
+            assert_eq!(&to_vec(&report.a), expected);
+        }
+    }
+
+    assert!(Target::from_order("7 1 9 3").unwrap().ranks(values).is_err());
+    assert!(Target::from_order("7 1 9 3 3").unwrap().ranks(values).is_err());
+    assert!(Target::Descending.ranks(&[4, 9, 4]).is_err());
+}
+
+fn replay_multi(values: &[N], k: usize, instructions: &[MultiInstruction]) -> bool {
+    let mut stacks = vec![VDStack::default(); k];
+    stacks[0] = values.iter().cloned().collect::<VDStack<N>>();
+
+    for instr in instructions {
+        execute_multi(instr, &mut stacks)
+    }
+
+    is_solved_multi(&stacks)
+}
+
+#[test]
+fn multi_stacks_generalize_the_puzzle() {
+    let classic = Rules { allowed: "pa,pb,sa,sb,ra,rb,rra,rrb".parse().unwrap(), ..Default::default() };
+    let stacks: &[&[N]] = &[&[1, 0, 2], &[3, 1, 0, 2], &[1, 0, 3, 2, 4], &[4, 3, 2, 1, 0]];
+
+    for values in stacks {
+        let stack = values.iter().cloned().collect::<VDStack<N>>();
+        let shortest = solution(&SolveStrategy::AStar, None, &classic, stack.clone()).unwrap().count();
+
+        let two = multi_solution(&SolveStrategy::AStar, 2, stack.clone());
+        let three = multi_solution(&SolveStrategy::AStar, 3, stack.clone());
+
+        assert!(replay_multi(values, 2, &two) && replay_multi(values, 3, &three));
+        assert_eq!(two.len(), shortest, "search over 2 stacks is not optimal on {:?}", values);
+        assert!(three.len() <= two.len());
+    }
+
+    let large = (0..60).map(|n| (n * 37) % 60).collect::<Vec<N>>();
+
+    for k in 2..6 {
+        let stack = large.iter().cloned().collect::<VDStack<N>>();
+        let instructions = multi_solution(&SolveStrategy::SmartInsert, k, stack);
+
+        assert!(instructions.iter().all(|instr| instr.stacks() <= k));
+        assert!(replay_multi(&large, k, &instructions), "insertion fails over {} stacks", k);
+    }
+}
+
+#[test]
+fn multi_instructions_round_trip() {
+    for instr in MultiInstruction::all(4) {
+        assert_eq!(instr.to_string().parse::<MultiInstruction>().unwrap(), instr);
+    }
+
+    assert_eq!("pab".parse::<MultiInstruction>().unwrap(), MultiInstruction::Push(0, 1));
+    assert_eq!("rrb".parse::<MultiInstruction>().unwrap(), MultiInstruction::RRotate(1));
+
+    for invalid in &["paa", "pa", "p", "s", "sA", "rrrb"] {
+        assert!(invalid.parse::<MultiInstruction>().is_err(), "{} parsed", invalid);
+    }
+}
+
+#[test]
+fn pushes_from_an_empty_stack_are_not_cancelled() {
+    use self::Instruction::*;
+
+    let stack = vec![1, 0, 2].into_iter().collect::<VDStack<N>>();
+    let report = Report::replay(stack.clone(), vec![PushA, PushB, PushA, SwapA], false);
+    assert_eq!(report.wasted_pairs, 1);
+
+    let report = Report::replay(stack, vec![PushB, PushA, RotateA, RRotateA], false);
+    assert_eq!(report.wasted_pairs, 2);
+}
+
+#[test]
+fn library_entry_points_follow_the_variant() {
+    use {Variant, solve, check};
+
+    let values = [30, -7, 12, 5];
+    let variant = Variant { target: Target::Descending, ..Default::default() };
+
+    let instructions = solve(&SolveStrategy::AStar, &values, &variant).unwrap();
+    let result = check(&values, &instructions, &variant).unwrap();
+    assert!(result.solved);
+    assert_eq!(result.a, vec![30, 12, 5, -7]);
+    assert!(!check(&values, &instructions, &Variant::default()).unwrap().solved);
+
+    assert!(solve(&SolveStrategy::AStar, &[1, 2, 1], &Variant::default()).is_err());
+    assert!(check(&[1, 2, 1], &[], &Variant::default()).is_err());
+
+    let restricted = Variant {
+        rules: Rules { allowed: "pa,pb,ra".parse().unwrap(), ..Default::default() },
+        ..Default::default()
+    };
+    assert!(check(&values, &[Instruction::SwapA], &restricted).is_err());
+}
+
+#[test]
+fn dead_ends_are_flagged_once_no_solution_fits() {
+    use self::Instruction::*;
+
+    let stack = vec![2, 1, 3, 0].into_iter().collect::<VDStack<N>>();
+    let dead_ends = |instructions| Report::replay(stack.clone(), instructions, true).trace
+        .iter().map(|step| step.dead_end).collect::<Vec<_>>();
+
+    assert_eq!(dead_ends(vec![PushB, PushB, SwapA, PushA]), vec![false, true, false, false]);
+
+    let solution = solution(&SolveStrategy::AStar, None, &Rules::default(), stack.clone())
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert!(dead_ends(solution).iter().all(|dead_end| !dead_end));
+}
@@ -1,29 +1,43 @@
 use std::collections::{VecDeque, hash_map::DefaultHasher};
 use std::hash::{Hash, Hasher};
+use std::cmp::Ordering;
 
 use super::*;
+use stack::{StackPair, SharedStacks};
 
+// Both stacks live in a single allocation, cloned for every neighbor
 #[derive(Default, Debug, Clone)]
-pub struct Node<S> {
-    pub a: S,
-    pub b: S,
-    pub instrs: VecDeque<Instruction>
+pub struct Node {
+    pub stacks: SharedStacks<N>,
+    pub instrs: VecDeque<Instruction>,
+    // Total cost of `instrs`
+    pub cost: u64,
 }
 
-impl<S: Stack<N>> PartialEq for Node<S> {
-    fn eq(&self, other: &Node<S>) -> bool {
-        self.a == other.a && self.b == other.b
+impl Node {
+    pub fn new(stack: &impl Stack<N>) -> Self {
+        Node { stacks: stack.iter().cloned().collect(), ..Default::default() }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        let stacks = &self.stacks;
+        stacks.len_b() == 0 && stacks.a().zip(stacks.a().skip(1)).all(|(x, y)| x <= y)
+    }
+}
+
+impl PartialEq for Node {
+    fn eq(&self, other: &Node) -> bool {
+        self.stacks == other.stacks
     }
 }
 
-impl<S: Stack<N>> Hash for Node<S> {
+impl Hash for Node {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.a.hash(state);
-        self.b.hash(state);
+        self.stacks.hash(state)
     }
 }
 
-impl<S: Stack<N>> Eq for Node<S> {}
+impl Eq for Node {}
 
 pub fn hash<T: Hash>(t: &T) -> u64 {
     let mut hasher = DefaultHasher::new();
@@ -31,7 +45,7 @@ pub fn hash<T: Hash>(t: &T) -> u64 {
     hasher.finish()
 }
 
-pub fn neighbors<S: Stack<N>>(node: Node<S>) -> impl Iterator<Item = Node<S>> {
+pub fn neighbors(node: Node, rules: Rules) -> impl Iterator<Item = Node> {
     use self::Instruction::*;
     type ValidateInstruction = fn(usize, usize, &Instruction) -> bool;
 
@@ -85,21 +99,108 @@ pub fn neighbors<S: Stack<N>>(node: Node<S>) -> impl Iterator<Item = Node<S>> {
         ),
     ];
 
-    let a_len = node.a.len();
-    let b_len = node.b.len();
+    let a_len = node.stacks.len_a();
+    let b_len = node.stacks.len_b();
     let last_instr = node.instrs.back().cloned().unwrap_or(PushB);
 
+    let Rules { costs, allowed } = rules;
+
+    // The rules above only keep a shortest solution of the classic puzzle:
+    // with uneven costs a longer one can be cheaper, and with fewer
+    // instructions the moves they prefer may be missing. Every move having
+    // an effect is tried then
+    let prune = costs.is_uniform() && allowed == InstructionSet::default();
+
     INSTRS.iter()
-        .filter(move |(_, valid_instr)| valid_instr(a_len, b_len, &last_instr))
-        .map(move |(instr, _)| transform_instr(instr, &node))
+        .filter(move |(instr, valid_instr)| {
+            allowed.contains(instr) && match prune {
+                true  => valid_instr(a_len, b_len, &last_instr),
+                false => has_effect(instr, a_len, b_len),
+            }
+        })
+        .map(move |(instr, _)| transform_instr(instr, &node, &costs))
+}
+
+fn has_effect(instr: &Instruction, a_len: usize, b_len: usize) -> bool {
+    use self::Instruction::*;
+
+    match *instr {
+        PushA                               => b_len > 0,
+        PushB                               => a_len > 0,
+        SwapA | RotateA | RRotateA          => a_len >= 2,
+        SwapB | RotateB | RRotateB          => b_len >= 2,
+        SwapBoth | RotateBoth | RRotateBoth => a_len >= 2 || b_len >= 2,
+    }
 }
 
-fn transform_instr<S: Stack<N>>(instr: &Instruction, n: &Node<S>) -> Node<S> {
+fn transform_instr(instr: &Instruction, n: &Node, costs: &Costs) -> Node {
     let mut node = n.clone();
 
     node.instrs.push_back(instr.clone());
+    node.cost += costs.of(instr) as u64;
 
-    execute(instr, &mut node.a, &mut node.b);
+    execute(instr, &mut node.stacks);
 
     node
 }
+
+// A lazy solver as a state machine: every step appends the instructions it
+// decided on, the solver is exhausted once a step reports it was the last
+pub trait Steps {
+    fn step(&mut self, out: &mut VecDeque<Instruction>) -> bool;
+}
+
+pub struct StepIter<T> {
+    steps: T,
+    pending: VecDeque<Instruction>,
+    exhausted: bool,
+}
+
+impl<T: Steps> StepIter<T> {
+    pub fn new(steps: T) -> Self {
+        StepIter { steps, pending: VecDeque::new(), exhausted: false }
+    }
+}
+
+impl<T: Steps> Iterator for StepIter<T> {
+    type Item = Instruction;
+
+    fn next(&mut self) -> Option<Instruction> {
+        loop {
+            if let Some(instr) = self.pending.pop_front() {
+                return Some(instr)
+            }
+            if self.exhausted {
+                return None
+            }
+            self.exhausted = !self.steps.step(&mut self.pending);
+        }
+    }
+}
+
+// Open set entry, `order` breaks ties between equally expensive nodes
+pub struct Queued {
+    pub node: Node,
+    pub order: u64,
+}
+
+// Reversed, so that the max-heap pops the cheapest and oldest node first
+impl Ord for Queued {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.node.cost, other.order).cmp(&(self.node.cost, self.order))
+    }
+}
+
+impl PartialOrd for Queued {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Queued {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Queued {}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use utils::{N, execute};
+use instruction::Instruction;
+
+use super::Stack;
+
+// Both stacks along with the instructions applied to them, which can be
+// undone without keeping copies of the earlier states
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    a: S,
+    b: S,
+    // Pushes from an empty stack leave the stacks untouched and must not
+    // be undone, the flag records whether the instruction had an effect
+    log: Vec<(Instruction, bool)>,
+}
+
+impl<S: Stack<N>> History<S> {
+    pub fn new(a: S, b: S) -> Self {
+        History { a, b, log: Vec::new() }
+    }
+
+    pub fn a(&self) -> &S {
+        &self.a
+    }
+
+    pub fn b(&self) -> &S {
+        &self.b
+    }
+
+    // Number of instructions applied so far
+    pub fn len(&self) -> usize {
+        self.log.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.log.is_empty()
+    }
+
+    pub fn last(&self) -> Option<&Instruction> {
+        self.log.last().map(|(instr, _)| instr)
+    }
+
+    pub fn apply(&mut self, instr: &Instruction) {
+        let lens = (self.a.len(), self.b.len());
+        execute(instr, &mut (&mut self.a, &mut self.b));
+
+        let is_push = instr == &Instruction::PushA || instr == &Instruction::PushB;
+        let applied = !is_push || lens != (self.a.len(), self.b.len());
+        self.log.push((instr.clone(), applied));
+    }
+
+    // Undoes the last k instructions, or all of them if there are fewer,
+    // and returns them in the order they were undone
+    pub fn undo(&mut self, k: usize) -> Vec<Instruction> {
+        let mut undone = Vec::with_capacity(k);
+
+        while undone.len() < k {
+            match self.log.pop() {
+                Some((instr, applied)) => {
+                    if applied {
+                        execute(&instr.inverse(), &mut (&mut self.a, &mut self.b))
+                    }
+                    undone.push(instr)
+                },
+                None => break,
+            }
+        }
+
+        undone
+    }
+
+    pub fn snapshot(&self) -> Snapshot<S> {
+        Snapshot { a: self.a.clone(), b: self.b.clone() }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot<S> {
+    pub a: S,
+    pub b: S,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    A, B
+}
+
+// Index from the top of either stack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub side: Side,
+    pub index: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Moved {
+    pub value: N,
+    pub from: Position,
+    pub to: Position,
+}
+
+impl<S: Stack<N>> Snapshot<S> {
+    // Values whose position differs in `after`, ordered by their position
+    // in `after`. Values are expected to be distinct
+    pub fn diff(&self, after: &Snapshot<S>) -> Vec<Moved> {
+        let before = positions(self).collect::<HashMap<_, _>>();
+
+        positions(after)
+            .filter_map(|(value, to)| {
+                let from = *before.get(&value)?;
+                match from == to {
+                    true  => None,
+                    false => Some(Moved { value, from, to }),
+                }
+            })
+            .collect()
+    }
+}
+
+fn positions<S: Stack<N>>(snapshot: &Snapshot<S>) -> impl Iterator<Item = (N, Position)> + '_ {
+    let at = |side| move |(index, value): (usize, &N)| (*value, Position { side, index });
+
+    snapshot.a.iter().enumerate().map(at(Side::A))
+        .chain(snapshot.b.iter().enumerate().map(at(Side::B)))
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let side = match self.side {
+            Side::A => "A",
+            Side::B => "B",
+        };
+
+        write!(f, "{}[{}]", side, self.index)
+    }
+}
+
+impl fmt::Display for Moved {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.value, self.from, self.to)
+    }
+}
@@ -2,7 +2,7 @@ use std::iter::FromIterator;
 use std::fmt::Display;
 use std::hash::Hash;
 
-pub trait Stack<T: PartialOrd>:
+pub trait Stack<T: PartialOrd + 'static>:
     Default + Clone + PartialEq +
     Display + Hash + FromIterator<T> +
     Send + 'static
@@ -14,23 +14,122 @@ pub trait Stack<T: PartialOrd>:
     fn rotate(&mut self);
     fn rrotate(&mut self);
 
-    // Helpers
+    // Values from the top, and the n-th one of them
+    fn iter(&self) -> impl Iterator<Item = &T>;
+    fn get(&self, n: usize) -> Option<&T>;
+
     fn len(&self) -> usize;
-    fn is_sorted(&self) -> bool;
-    fn sorted_at(&self) -> Option<usize>;
-    fn minimum(&self) -> Option<(&T, usize)>;
-    fn maximum(&self) -> Option<(&T, usize)>;
 
-    fn rotate_n(&mut self, n: usize);
+    // An empty stack able to take every value of `self`, which is what B
+    // starts as. Backends with a buffer size it from A, so that B never
+    // has to grow
+    fn empty_like(&self) -> Self {
+        Self::default()
+    }
+
+    // Helpers, backends may override them when they can do better
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn is_sorted(&self) -> bool {
+        self.iter().zip(self.iter().skip(1))
+            .all(|(a, b)| a <= b)
+    }
+
+    // Index at which the stack would be sorted if rotated, if there is one
+    fn sorted_at(&self) -> Option<usize> {
+        let mut pivot = None;
+
+        let lefts = self.iter();
+        let rights = self.iter().skip(1).chain(self.iter().take(1));
+        let indexes = 1..;
+
+        for ((a, b), i) in lefts.zip(rights).zip(indexes) {
+            if a > b {
+                if pivot.is_some() { return None }
+                else { pivot = Some(i) }
+            }
+        }
+
+        // Without any descent the values are all equal, or there is at most
+        // one of them
+        Some(pivot.unwrap_or(0))
+    }
+
+    fn minimum(&self) -> Option<(&T, usize)> {
+        self.iter().zip(0..).fold(None, |min, (e, i)| {
+            match min {
+                Some((p, _)) if e > p => min,
+                _                     => Some((e, i))
+            }
+        })
+    }
 
-    fn insert_index(&self, t: &T) -> Option<usize>;
-    fn peek(&self, n: isize) -> &T;
+    fn maximum(&self) -> Option<(&T, usize)> {
+        self.iter().zip(0..).fold(None, |min, (e, i)| {
+            match min {
+                Some((p, _)) if e < p => min,
+                _                     => Some((e, i))
+            }
+        })
+    }
+
+    // Rotates n times, modulo the length of the stack
+    fn rotate_n(&mut self, n: usize) {
+        if self.is_empty() { return }
+
+        for _ in 0..n % self.len() { self.rotate() }
+    }
+
+    // Rotates for positive n and reverse rotates for negative n
+    fn rotate_by(&mut self, n: isize) {
+        if let Some(index) = self.wrap_index(n) {
+            self.rotate_n(index)
+        }
+    }
+
+    fn insert_index(&self, t: &T) -> Option<usize> {
+        let lefts = self.iter();
+        let rights = self.iter().skip(1).chain(self.iter().take(1));
+
+        lefts.zip(rights).zip(1..).find_map(|((l, r), i)| {
+            match l >= t && r <= t {
+                true => Some(i),
+                false => None
+            }
+        })
+    }
+
+    // Index of the n-th value from the top, or of the -n-th from the bottom
+    // for negative n, wrapping around: None only if the stack is empty
+    fn wrap_index(&self, n: isize) -> Option<usize> {
+        match self.len() {
+            0   => None,
+            len => Some(n.rem_euclid(len as isize) as usize),
+        }
+    }
+
+    fn peek(&self, n: isize) -> Option<&T> {
+        self.wrap_index(n).and_then(|index| self.get(index))
+    }
 }
 
 mod linked_list;
 mod vec_deque;
 mod vec;
+mod ring;
+mod small;
+mod pair;
+mod history;
 
 pub use self::linked_list::LLStack;
 pub use self::vec_deque::VDStack;
 pub use self::vec::VecStack;
+pub use self::ring::RingStack;
+pub use self::small::{SmallStack, SMALL_STACK_CAPACITY};
+pub use self::pair::{StackPair, SharedStacks};
+pub use self::history::{History, Snapshot, Moved, Position, Side};
+
+#[cfg(test)]
+mod tests;
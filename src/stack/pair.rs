@@ -0,0 +1,141 @@
+extern crate smallvec;
+
+use self::smallvec::SmallVec;
+
+use std::iter::FromIterator;
+
+use super::{Stack, SMALL_STACK_CAPACITY};
+
+// Both stacks of the problem, operated on as a whole
+pub trait StackPair<T> {
+    fn push_a(&mut self);
+    fn push_b(&mut self);
+    fn swap_a(&mut self);
+    fn swap_b(&mut self);
+    fn rotate_a(&mut self);
+    fn rotate_b(&mut self);
+    fn rrotate_a(&mut self);
+    fn rrotate_b(&mut self);
+
+    fn len_a(&self) -> usize;
+    fn len_b(&self) -> usize;
+}
+
+// Two independent stacks
+impl<'a, T, S> StackPair<T> for (&'a mut S, &'a mut S)
+where
+    T: PartialOrd + 'static,
+    S: Stack<T>
+{
+    fn push_a(&mut self) {
+        if let Some(n) = self.1.pop() { self.0.push(n) }
+    }
+
+    fn push_b(&mut self) {
+        if let Some(n) = self.0.pop() { self.1.push(n) }
+    }
+
+    fn swap_a(&mut self)    { self.0.swap() }
+    fn swap_b(&mut self)    { self.1.swap() }
+    fn rotate_a(&mut self)  { self.0.rotate() }
+    fn rotate_b(&mut self)  { self.1.rotate() }
+    fn rrotate_a(&mut self) { self.0.rrotate() }
+    fn rrotate_b(&mut self) { self.1.rrotate() }
+
+    fn len_a(&self) -> usize { self.0.len() }
+    fn len_b(&self) -> usize { self.1.len() }
+}
+
+// A and B in a single allocation, laid out as
+//   [B bottom .. B top | A top .. A bottom]
+// so that pushes only move the boundary, rotations shift one side. Like
+// SmallStack, values live inline up to SMALL_STACK_CAPACITY
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct SharedStacks<T> {
+    values: SmallVec<[T; SMALL_STACK_CAPACITY]>,
+    boundary: usize,
+}
+
+impl<T> SharedStacks<T> {
+    // Values of A from the top
+    pub fn a(&self) -> impl Iterator<Item = &T> {
+        self.values[self.boundary..].iter()
+    }
+
+    // Values of B from the top
+    pub fn b(&self) -> impl Iterator<Item = &T> {
+        self.values[..self.boundary].iter().rev()
+    }
+
+    pub fn to_stacks<S>(&self) -> (S, S)
+    where
+        T: Clone + PartialOrd + 'static,
+        S: Stack<T>
+    {
+        (self.a().cloned().collect(), self.b().cloned().collect())
+    }
+}
+
+impl<T> StackPair<T> for SharedStacks<T> {
+    fn push_a(&mut self) {
+        if self.boundary > 0 { self.boundary -= 1 }
+    }
+
+    fn push_b(&mut self) {
+        if self.boundary < self.values.len() { self.boundary += 1 }
+    }
+
+    fn swap_a(&mut self) {
+        if self.len_a() >= 2 {
+            self.values.swap(self.boundary, self.boundary + 1)
+        }
+    }
+
+    fn swap_b(&mut self) {
+        if self.len_b() >= 2 {
+            self.values.swap(self.boundary - 1, self.boundary - 2)
+        }
+    }
+
+    fn rotate_a(&mut self) {
+        if self.len_a() > 0 {
+            self.values[self.boundary..].rotate_left(1)
+        }
+    }
+
+    fn rotate_b(&mut self) {
+        if self.len_b() > 0 {
+            self.values[..self.boundary].rotate_right(1)
+        }
+    }
+
+    fn rrotate_a(&mut self) {
+        if self.len_a() > 0 {
+            self.values[self.boundary..].rotate_right(1)
+        }
+    }
+
+    fn rrotate_b(&mut self) {
+        if self.len_b() > 0 {
+            self.values[..self.boundary].rotate_left(1)
+        }
+    }
+
+    fn len_a(&self) -> usize {
+        self.values.len() - self.boundary
+    }
+
+    fn len_b(&self) -> usize {
+        self.boundary
+    }
+}
+
+// Every value starts in A
+impl<T> FromIterator<T> for SharedStacks<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>
+    {
+        SharedStacks { values: SmallVec::from_iter(iter), boundary: 0 }
+    }
+}
@@ -0,0 +1,202 @@
+use std::iter::FromIterator;
+use std::hash::{Hash, Hasher};
+use std::fmt;
+use std::mem;
+
+// Ring buffer holding the stack from `head` (top) onwards, the free slots
+// following the bottom value. Pushes and pops only move `head`, a rotation
+// moves a single value across the free slots, or none once the buffer is
+// full. The buffer doubles whenever a push finds it full
+#[derive(Debug, Default, Clone)]
+pub struct RingStack<T> {
+    buf: Vec<T>,
+    head: usize,
+    len: usize,
+}
+
+impl<T: Default> RingStack<T> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut buf = Vec::with_capacity(capacity);
+        buf.resize_with(capacity, T::default);
+
+        RingStack { buf, head: 0, len: 0 }
+    }
+
+    fn take(&mut self, index: usize) -> T {
+        mem::take(&mut self.buf[index])
+    }
+
+    // Lays the values out from the start of a buffer twice as large
+    fn grow(&mut self) {
+        let capacity = std::cmp::max(4, 2 * self.capacity());
+        let mut buf = Vec::with_capacity(capacity);
+
+        for n in 0..self.len {
+            let index = self.index(n);
+            buf.push(self.take(index));
+        }
+        buf.resize_with(capacity, T::default);
+
+        self.buf = buf;
+        self.head = 0;
+    }
+}
+
+impl<T> RingStack<T> {
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    fn is_full(&self) -> bool {
+        self.len == self.capacity()
+    }
+
+    // Physical index of the n-th value from the top, `n` being less than
+    // the capacity
+    fn index(&self, n: usize) -> usize {
+        match self.head + n {
+            index if index >= self.capacity() => index - self.capacity(),
+            index                             => index,
+        }
+    }
+
+    // The values up to the end of the buffer, then the ones wrapped around
+    fn values(&self) -> impl Iterator<Item = &T> {
+        let end = std::cmp::min(self.head + self.len, self.capacity());
+        let wrapped = self.head + self.len - end;
+
+        self.buf[self.head..end].iter().chain(self.buf[..wrapped].iter())
+    }
+}
+
+impl<T> super::Stack<T> for RingStack<T>
+where
+    T: PartialOrd + fmt::Display + Default + Hash + Clone + Send + 'static
+{
+    fn push(&mut self, n: T) {
+        if self.is_full() {
+            self.grow()
+        }
+
+        self.head = self.index(self.capacity() - 1);
+        self.buf[self.head] = n;
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.len == 0 { return None }
+
+        let head = self.head;
+        self.head = self.index(1);
+        self.len -= 1;
+
+        Some(self.take(head))
+    }
+
+    fn swap(&mut self) {
+        if self.len >= 2 {
+            let (first, second) = (self.index(0), self.index(1));
+            self.buf.swap(first, second)
+        }
+    }
+
+    // The top value moves to the first free slot after the bottom one
+    fn rotate(&mut self) {
+        if self.len < 2 { return }
+
+        if !self.is_full() {
+            let (head, bottom) = (self.head, self.index(self.len));
+            let top = self.take(head);
+            self.buf[bottom] = top;
+        }
+        self.head = self.index(1);
+    }
+
+    // The bottom value moves to the free slot right above the top one
+    fn rrotate(&mut self) {
+        if self.len < 2 { return }
+
+        let head = self.index(self.capacity() - 1);
+        if !self.is_full() {
+            let bottom = self.index(self.len - 1);
+            self.buf[head] = self.take(bottom);
+        }
+        self.head = head;
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        self.values()
+    }
+
+    fn get(&self, n: usize) -> Option<&T> {
+        match n < self.len {
+            true  => Some(&self.buf[self.index(n)]),
+            false => None,
+        }
+    }
+
+    // Moves the values the shortest way around, or only `head` once the
+    // buffer is full
+    fn rotate_n(&mut self, n: usize) {
+        if self.len == 0 { return }
+
+        let n = n % self.len;
+        match (self.is_full(), n <= self.len - n) {
+            (true, _)      => self.head = self.index(n),
+            (false, true)  => for _ in 0..n { self.rotate() },
+            (false, false) => for _ in n..self.len { self.rrotate() },
+        }
+    }
+
+    fn empty_like(&self) -> Self {
+        RingStack::with_capacity(self.capacity())
+    }
+}
+
+impl<T: PartialEq> PartialEq for RingStack<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.values().eq(other.values())
+    }
+}
+
+impl<T: Eq> Eq for RingStack<T> {}
+
+// Hashes the values only, stacks that are equal can differ in layout
+impl<T: Hash> Hash for RingStack<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        for value in self.values() {
+            value.hash(state)
+        }
+    }
+}
+
+impl<T> FromIterator<T> for RingStack<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>
+    {
+        let buf = Vec::from_iter(iter);
+        RingStack { head: 0, len: buf.len(), buf }
+    }
+}
+
+impl<T: fmt::Display + PartialOrd> fmt::Display for RingStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut it = self.values();
+
+        match it.next() {
+            None    => write!(f, "Empty")?,
+            Some(n) => {
+                write!(f, "{}", n)?;
+                for n in it { write!(f, " {}", n)?; }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+extern crate smallvec;
+
+use self::smallvec::SmallVec;
+
+use std::iter::FromIterator;
+use std::hash::Hash;
+use std::fmt;
+
+pub const SMALL_STACK_CAPACITY: usize = 16;
+
+// Stored bottom to top so that pushes and pops happen at the end, values
+// live inline up to SMALL_STACK_CAPACITY and spill to the heap past it
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
+pub struct SmallStack<T>(SmallVec<[T; SMALL_STACK_CAPACITY]>);
+
+impl<T> SmallStack<T> {
+    // Values from the top
+    fn values(&self) -> impl Iterator<Item = &T> {
+        self.0.iter().rev()
+    }
+}
+
+impl<T> super::Stack<T> for SmallStack<T>
+where
+    T: PartialOrd + fmt::Display + Default + Hash + Clone + Send + 'static
+{
+    fn push(&mut self, n: T) {
+        self.0.push(n)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    fn swap(&mut self) {
+        let len = self.0.len();
+
+        if len >= 2 {
+            self.0.swap(len - 1, len - 2)
+        }
+    }
+
+    fn rotate(&mut self) {
+        if !self.0.is_empty() {
+            self.0.rotate_right(1)
+        }
+    }
+
+    fn rrotate(&mut self) {
+        if !self.0.is_empty() {
+            self.0.rotate_left(1)
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        self.values()
+    }
+
+    fn get(&self, n: usize) -> Option<&T> {
+        let len = self.0.len();
+
+        match n < len {
+            true  => Some(&self.0[len - 1 - n]),
+            false => None,
+        }
+    }
+
+    fn rotate_n(&mut self, n: usize) {
+        if self.0.is_empty() { return }
+
+        let n = n % self.0.len();
+        self.0.rotate_right(n)
+    }
+}
+
+// The first value of the iterator ends up on top
+impl<T> FromIterator<T> for SmallStack<T> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>
+    {
+        let mut values = SmallVec::from_iter(iter);
+        values.reverse();
+        SmallStack(values)
+    }
+}
+
+impl<T: fmt::Display + PartialOrd> fmt::Display for SmallStack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut it = self.values();
+
+        match it.next() {
+            None    => write!(f, "Empty")?,
+            Some(n) => {
+                write!(f, "{}", n)?;
+                for n in it { write!(f, " {}", n)?; }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,247 @@
+use super::*;
+
+use utils::{N, execute};
+use instruction::Instruction;
+use generator::Rng;
+
+const CASES: u64 = 500;
+const MAX_SIZE: u64 = 20;
+const MAX_INSTRUCTIONS: u64 = 60;
+
+// Everything observable through the helpers of a single stack
+#[derive(Debug, PartialEq)]
+struct Observation {
+    display: String,
+    len: usize,
+    minimum: Option<(N, usize)>,
+    maximum: Option<(N, usize)>,
+    sorted_at: Option<usize>,
+    insert_indexes: Vec<Option<usize>>,
+    peeks: Vec<Option<N>>,
+}
+
+fn observe<S: Stack<N>>(stack: &S) -> Observation {
+    let len = stack.len() as isize;
+    let probes = 0..(MAX_SIZE as N + 2);
+    let peeks = (-2 * len - 1..2 * len + 1).map(|n| stack.peek(n).cloned()).collect();
+
+    Observation {
+        display: stack.to_string(),
+        len: stack.len(),
+        minimum: stack.minimum().map(|(n, i)| (*n, i)),
+        maximum: stack.maximum().map(|(n, i)| (*n, i)),
+        sorted_at: stack.sorted_at(),
+        insert_indexes: probes.map(|n| stack.insert_index(&n)).collect(),
+        peeks,
+    }
+}
+
+struct Run<S> {
+    a: S,
+    b: S,
+}
+
+impl<S: Stack<N>> Run<S> {
+    fn new(values: &[N]) -> Self {
+        Run { a: values.iter().cloned().collect(), b: S::default() }
+    }
+
+    fn step(&mut self, instr: &Instruction) {
+        execute(instr, &mut (&mut self.a, &mut self.b))
+    }
+
+    fn observe(&self) -> (Observation, Observation) {
+        (observe(&self.a), observe(&self.b))
+    }
+}
+
+fn random_values(rng: &mut Rng) -> Vec<N> {
+    let size = rng.below(MAX_SIZE + 1);
+    let with_duplicates = rng.below(2) == 0;
+
+    match with_duplicates {
+        true  => (0..size).map(|_| rng.below(size) as N).collect(),
+        false => {
+            let mut values = (0..size as N).collect::<Vec<_>>();
+            rng.shuffle(&mut values);
+            values
+        }
+    }
+}
+
+#[test]
+fn backends_are_interchangeable() {
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        let values = random_values(&mut rng);
+
+        let mut ll = Run::<LLStack<N>>::new(&values);
+        let mut vd = Run::<VDStack<N>>::new(&values);
+        let mut vec = Run::<VecStack<N>>::new(&values);
+        let mut ring = Run::<RingStack<N>>::new(&values);
+        let mut small = Run::<SmallStack<N>>::new(&values);
+
+        let mut trace = Vec::new();
+
+        for _ in 0..rng.below(MAX_INSTRUCTIONS + 1) {
+            let instr = Instruction::ALL[rng.below(11) as usize].clone();
+
+            ll.step(&instr);
+            vd.step(&instr);
+            vec.step(&instr);
+            ring.step(&instr);
+            small.step(&instr);
+            trace.push(instr);
+
+            let expected = ll.observe();
+            assert_eq!(expected, vd.observe(),
+                "VDStack diverged (seed {}, stack {:?}, instructions {:?})", seed, values, trace);
+            assert_eq!(expected, vec.observe(),
+                "VecStack diverged (seed {}, stack {:?}, instructions {:?})", seed, values, trace);
+            assert_eq!(expected, ring.observe(),
+                "RingStack diverged (seed {}, stack {:?}, instructions {:?})", seed, values, trace);
+            assert_eq!(expected, small.observe(),
+                "SmallStack diverged (seed {}, stack {:?}, instructions {:?})", seed, values, trace);
+        }
+    }
+}
+
+#[test]
+fn rotate_n_matches_repeated_rotations() {
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        let values = random_values(&mut rng);
+        // Past the length of the stack rotations wrap around
+        let n = rng.below(3 * values.len() as u64 + 1) as usize;
+
+        let mut rotated = values.iter().cloned().collect::<LLStack<N>>();
+        for _ in 0..n { rotated.rotate() }
+
+        let mut ll = values.iter().cloned().collect::<LLStack<N>>();
+        let mut vd = values.iter().cloned().collect::<VDStack<N>>();
+        let mut vec = values.iter().cloned().collect::<VecStack<N>>();
+        let mut ring = values.iter().cloned().collect::<RingStack<N>>();
+        let mut small = values.iter().cloned().collect::<SmallStack<N>>();
+        // Growing from the default leaves free slots, which rotations have
+        // to cross
+        let mut sparse_ring = RingStack::default();
+        for &value in values.iter().rev() { sparse_ring.push(value) }
+        ll.rotate_n(n);
+        vd.rotate_n(n);
+        vec.rotate_n(n);
+        ring.rotate_n(n);
+        small.rotate_n(n);
+        sparse_ring.rotate_n(n);
+
+        let expected = observe(&rotated);
+        assert_eq!(expected, observe(&ll), "seed {}", seed);
+        assert_eq!(expected, observe(&vd), "seed {}", seed);
+        assert_eq!(expected, observe(&vec), "seed {}", seed);
+        assert_eq!(expected, observe(&ring), "seed {}", seed);
+        assert_eq!(expected, observe(&small), "seed {}", seed);
+        assert_eq!(expected, observe(&sparse_ring), "seed {}", seed);
+    }
+}
+
+#[test]
+fn rotate_by_matches_repeated_rotations() {
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        let values = random_values(&mut rng);
+        let len = values.len() as i64;
+        let n = rng.below(6 * len as u64 + 1) as i64 - 3 * len;
+
+        let mut rotated = values.iter().cloned().collect::<LLStack<N>>();
+        match n < 0 {
+            true  => for _ in 0..-n { rotated.rrotate() },
+            false => for _ in 0..n { rotated.rotate() },
+        }
+
+        let mut ll = values.iter().cloned().collect::<LLStack<N>>();
+        let mut vd = values.iter().cloned().collect::<VDStack<N>>();
+        let mut vec = values.iter().cloned().collect::<VecStack<N>>();
+        let mut ring = values.iter().cloned().collect::<RingStack<N>>();
+        let mut small = values.iter().cloned().collect::<SmallStack<N>>();
+        ll.rotate_by(n as isize);
+        vd.rotate_by(n as isize);
+        vec.rotate_by(n as isize);
+        ring.rotate_by(n as isize);
+        small.rotate_by(n as isize);
+
+        let expected = observe(&rotated);
+        assert_eq!(expected, observe(&ll), "seed {}", seed);
+        assert_eq!(expected, observe(&vd), "seed {}", seed);
+        assert_eq!(expected, observe(&vec), "seed {}", seed);
+        assert_eq!(expected, observe(&ring), "seed {}", seed);
+        assert_eq!(expected, observe(&small), "seed {}", seed);
+    }
+}
+
+#[test]
+fn shared_stacks_match_separate_stacks() {
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        let values = random_values(&mut rng);
+
+        let mut separate = Run::<VDStack<N>>::new(&values);
+        let mut shared = values.iter().cloned().collect::<SharedStacks<N>>();
+
+        for _ in 0..rng.below(MAX_INSTRUCTIONS + 1) {
+            let instr = Instruction::ALL[rng.below(11) as usize].clone();
+
+            separate.step(&instr);
+            execute(&instr, &mut shared);
+
+            let (a, b) = shared.to_stacks::<VDStack<N>>();
+            assert_eq!((&separate.a, &separate.b), (&a, &b),
+                "SharedStacks diverged (seed {}, stack {:?}, instruction {:?})", seed, values, instr);
+            assert_eq!((separate.a.len(), separate.b.len()), (shared.len_a(), shared.len_b()));
+        }
+    }
+}
+
+#[test]
+fn undo_restores_snapshots() {
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        let values = random_values(&mut rng);
+
+        let stack = values.iter().cloned().collect::<VDStack<N>>();
+        let mut history = History::new(stack, VDStack::default());
+        let mut snapshots = vec![history.snapshot()];
+
+        for _ in 0..rng.below(MAX_INSTRUCTIONS + 1) {
+            history.apply(&Instruction::ALL[rng.below(11) as usize]);
+            snapshots.push(history.snapshot());
+        }
+
+        while !history.is_empty() {
+            let k = rng.below(4) as usize + 1;
+            let undone = history.undo(k);
+            let expected = &snapshots[history.len()];
+
+            assert!(undone.len() <= k, "seed {}", seed);
+            assert_eq!(expected, &history.snapshot(),
+                "seed {}, stack {:?}, undone {:?}", seed, values, undone);
+
+            snapshots.truncate(history.len() + 1);
+        }
+    }
+}
+
+#[test]
+fn diff_reports_moved_values() {
+    let at = |side, index| Position { side, index };
+
+    let stack = vec![3, 1, 2].into_iter().collect::<VDStack<N>>();
+    let mut history = History::new(stack, VDStack::default());
+    let before = history.snapshot();
+    history.apply(&Instruction::PushB);
+
+    assert_eq!(before.diff(&history.snapshot()), vec![
+        Moved { value: 1, from: at(Side::A, 1), to: at(Side::A, 0) },
+        Moved { value: 2, from: at(Side::A, 2), to: at(Side::A, 1) },
+        Moved { value: 3, from: at(Side::A, 0), to: at(Side::B, 0) },
+    ]);
+    assert!(before.diff(&before).is_empty());
+}
@@ -45,68 +45,19 @@ where
         self.0.len()
     }
 
-    fn is_sorted(&self) -> bool {
-        self.0.iter().zip(self.0.iter().skip(1))
-            .all(|(a, b)| a <= b)
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
     }
 
-    fn sorted_at(&self) -> Option<usize> {
-        let mut pivot = None;
-
-        let lefts = self.0.iter();
-        let rights = self.0.iter().skip(1).chain(self.0.iter().take(1));
-        let indexes = 1..;
-
-        for ((a, b), i) in lefts.zip(rights).zip(indexes) {
-            if a > b {
-                if pivot.is_some() { return None }
-                else { pivot = Some(i) }
-            }
-        }
-
-        pivot
-    }
-
-    fn minimum(&self) -> Option<(&T, usize)> {
-        self.0.iter().zip(0..).fold(None, |min, (e, i)| {
-            match min {
-                Some((p, _)) if e > p => min,
-                _                     => Some((e, i))
-            }
-        })
+    fn get(&self, n: usize) -> Option<&T> {
+        self.0.get(n)
     }
 
-    fn maximum(&self) -> Option<(&T, usize)> {
-        self.0.iter().zip(0..).fold(None, |min, (e, i)| {
-            match min {
-                Some((p, _)) if e < p => min,
-                _                     => Some((e, i))
-            }
-        })
-    }
-
-    fn rotate_n(&mut self, n: usize)  {
-        let mut high = self.0.split_off(n);
-        ::std::mem::swap(&mut self.0, &mut high);
-        self.0.append(&mut high)
-    }
-
-    fn insert_index(&self, t: &T) -> Option<usize> {
-        let lefts = self.0.iter();
-        let rights = self.0.iter().skip(1).chain(self.0.iter().take(1));
-
-        lefts.zip(rights).zip(1..).find_map(|((l, r), i)| {
-            match l >= t && r <= t {
-                true => Some(i),
-                false => None
-            }
-        })
-    }
+    fn rotate_n(&mut self, n: usize) {
+        if self.0.is_empty() { return }
 
-    fn peek(&self, n: isize) -> &T {
-        let index = if n < 0 { self.0.len() - (n % (self.0.len() as isize)).abs() as usize - 1 }
-                    else     { n as usize % self.0.len() };
-        self.0.get(index).unwrap()
+        let n = n % self.0.len();
+        self.0.rotate_left(n)
     }
 }
 
@@ -0,0 +1,67 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use utils::N;
+
+// Order A must end up in, top first, once B is empty
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Target {
+    #[default]
+    Ascending,
+    Descending,
+    Order(Vec<N>),
+}
+
+impl Target {
+    // Whitespace separated values, listed from the top of A
+    pub fn from_order(s: &str) -> Result<Self, String> {
+        let order = s.split_whitespace()
+            .map(|n| n.parse().map_err(|_| format!("invalid value in target: {}", n)))
+            .collect::<Result<Vec<N>, _>>()?;
+
+        Ok(Target::Order(order))
+    }
+
+    // Maps every value to one that sorts like the value's place in the
+    // target: solving the mapped stack in ascending order reaches the target.
+    // Duplicates would have no place of their own and are rejected
+    pub fn ranks(&self, values: &[N]) -> Result<Vec<N>, String> {
+        let mut seen = HashSet::with_capacity(values.len());
+        if let Some(n) = values.iter().find(|n| !seen.insert(*n)) {
+            return Err(format!("duplicate value {}", n))
+        }
+
+        match self {
+            Target::Ascending  => Ok(values.to_vec()),
+            // Flipping every bit reverses the order without overflowing
+            Target::Descending => Ok(values.iter().map(|n| !n).collect()),
+            Target::Order(order) => {
+                let places = order.iter().enumerate()
+                    .map(|(place, n)| (*n, place as N))
+                    .collect::<HashMap<_, _>>();
+
+                let (mut expected, mut actual) = (order.clone(), values.to_vec());
+                expected.sort();
+                actual.sort();
+
+                if places.len() != order.len() || expected != actual {
+                    return Err(String::from("the target is not a permutation of the stack"))
+                }
+
+                Ok(values.iter().map(|n| places[n]).collect())
+            },
+        }
+    }
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ascending"  | "asc"  => Ok(Target::Ascending),
+            "descending" | "desc" => Ok(Target::Descending),
+            invalid => Err(format!("unknown target '{}', expected ascending or descending", invalid))
+        }
+    }
+}
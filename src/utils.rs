@@ -1,52 +1,60 @@
-use stack::Stack;
+use stack::{Stack, StackPair};
 use instruction::Instruction;
 
-pub type N = u32;
+pub type N = i64;
 
-pub fn execute<S>(instr: &Instruction, a: &mut S, b: &mut S)
+// Separate stacks are executed on as `&mut (&mut a, &mut b)`
+pub fn execute<P>(instr: &Instruction, stacks: &mut P)
 where
-    S: Stack<N> + Sized
+    P: StackPair<N>
 {
     use self::Instruction::*;
 
     match instr {
-        PushA  => if let Some(n) = b.pop() { a.push(n) },
-        PushB  => if let Some(n) = a.pop() { b.push(n) },
+        PushA  => stacks.push_a(),
+        PushB  => stacks.push_b(),
 
-        SwapA    => a.swap(),
-        SwapB    => b.swap(),
-        SwapBoth => { a.swap(); b.swap() },
+        SwapA    => stacks.swap_a(),
+        SwapB    => stacks.swap_b(),
+        SwapBoth => { stacks.swap_a(); stacks.swap_b() },
 
-        RotateA    => a.rotate(),
-        RotateB    => b.rotate(),
-        RotateBoth => { a.rotate(); b.rotate() },
+        RotateA    => stacks.rotate_a(),
+        RotateB    => stacks.rotate_b(),
+        RotateBoth => { stacks.rotate_a(); stacks.rotate_b() },
 
-        RRotateA    => a.rrotate(),
-        RRotateB    => b.rrotate(),
-        RRotateBoth => { a.rrotate(); b.rrotate(); }
+        RRotateA    => stacks.rrotate_a(),
+        RRotateB    => stacks.rrotate_b(),
+        RRotateBoth => { stacks.rrotate_a(); stacks.rrotate_b(); }
     }
 }
 
-use std::ops::{Generator, GeneratorState};
-
-pub struct IterGen<G: Generator>(pub G);
-
-impl<T, G> Iterator for IterGen<G>
-where
-    G: Generator<Yield = T, Return = ()>
-{
-    type Item = T;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match unsafe { self.0.resume() } {
-            GeneratorState::Yielded(element) => Some(element),
-            _                                => None
-        }
-    }
+pub fn is_solved<S: Stack<N>>(a: &S, b: &S) -> bool {
+    b.len() == 0 && a.is_sorted()
 }
 
-pub fn repeat_n<T: Clone>(t: T, n: usize) -> impl Iterator<Item = T> {
-    use std::iter::repeat;
+// Lower bound on the number of moves still required to reach the solved
+// state: every element left in B needs a push and a rotated A needs to be
+// rotated back into place. Pushing B back alone only solves the stacks if
+// its values land on top of A in order, any other move adds to the count
+pub fn min_moves_left<S: Stack<N>>(a: &S, b: &S) -> usize {
+    use std::cmp::min;
+
+    let mut pushed_back = to_vec(b);
+    pushed_back.reverse();
+    pushed_back.extend(a.iter().cloned());
+    let fits = pushed_back.windows(2).all(|pair| pair[0] <= pair[1]);
+
+    let extra_moves = match (b.len(), a.is_sorted(), a.sorted_at()) {
+        (0, true, _)            => 0,
+        (0, false, Some(pivot)) => min(pivot, a.len() - pivot),
+        (0, false, None)        => 1,
+        _ if fits               => 0,
+        _                       => 1,
+    };
+
+    b.len() + extra_moves
+}
 
-    repeat(t).take(n)
+pub fn to_vec<S: Stack<N>>(stack: &S) -> Vec<N> {
+    stack.iter().cloned().collect()
 }
@@ -0,0 +1,98 @@
+use super::termion::{self, clear, color, cursor, style};
+
+use std::io::{self, Write};
+
+use push_swap::utils::{N, to_vec};
+use push_swap::stack::Stack;
+
+use super::Player;
+
+pub fn frame<S: Stack<N>>(out: &mut impl Write, player: &Player<S>) -> io::Result<()> {
+    let (width, height) = match termion::terminal_size() {
+        Ok((width, height)) if width > 0 && height > 0 => (width as usize, height as usize),
+        _                                              => (80, 24),
+    };
+
+    let replay = &player.replay;
+    let column_width = width.saturating_sub(3) / 2;
+    // Header, column titles and footer
+    let rows = height.saturating_sub(3);
+
+    write!(out, "{}{}", clear::All, cursor::Goto(1, 1))?;
+
+    write!(out, "{}Step {}/{}{}  {}  {:.1} instr/s  last: {}",
+        style::Bold, replay.cursor(), replay.len(), style::Reset,
+        match player.playing { true => "playing", false => "paused" },
+        player.speed,
+        replay.last_instruction().map(|i| i.to_string()).unwrap_or_else(|| "-".into())
+    )?;
+
+    let a = to_vec(replay.a());
+    let b = to_vec(replay.b());
+
+    write!(out, "{}{:width$} | {}",
+        cursor::Goto(1, 2),
+        column_title("A", a.len(), rows), column_title("B", b.len(), rows),
+        width=column_width
+    )?;
+
+    let a_rows = sample(&a, rows);
+    let b_rows = sample(&b, rows);
+
+    for row in 0..rows {
+        write!(out, "{}", cursor::Goto(1, row as u16 + 3))?;
+        write_bar(out, a_rows.get(row).cloned(), column_width, player.value_range)?;
+        write!(out, " | ")?;
+        write_bar(out, b_rows.get(row).cloned(), column_width, player.value_range)?;
+    }
+
+    let help = match player.jump_input {
+        Some(ref input) => format!("Jump to instruction: {}_ (enter to confirm)", input),
+        None            => String::from(
+            "space: play/pause  ←/→: step  ↑/↓: speed  g: jump  home/end  q: quit"
+        ),
+    };
+    write!(out, "{}{}", cursor::Goto(1, height as u16), help)?;
+
+    out.flush()
+}
+
+fn column_title(name: &str, len: usize, rows: usize) -> String {
+    match len > rows {
+        true  => format!("{} ({}, sampled)", name, len),
+        false => format!("{} ({})", name, len),
+    }
+}
+
+// Picks evenly spaced values so that the stack fits in `rows` lines
+fn sample(values: &[N], rows: usize) -> Vec<N> {
+    match values.len() > rows {
+        true  => (0..rows).map(|row| values[row * values.len() / rows]).collect(),
+        false => values.to_vec(),
+    }
+}
+
+fn write_bar(out: &mut impl Write, value: Option<N>, width: usize, (min, max): (N, N))
+    -> io::Result<()>
+{
+    let value = match value {
+        Some(value) => value,
+        None        => return write!(out, "{:width$}", "", width=width),
+    };
+
+    let ratio = match max > min {
+        true  => value.abs_diff(min) as f32 / max.abs_diff(min) as f32,
+        false => 1.,
+    };
+
+    let bar_len = 1 + (ratio * width.saturating_sub(1) as f32) as usize;
+    let bar = "█".repeat(bar_len);
+
+    // Gradient from blue (small values) to red (large values)
+    let (r, g, b) = ((255. * ratio) as u8, 64, (255. * (1. - ratio)) as u8);
+
+    write!(out, "{}{}{}{:pad$}",
+        color::Fg(color::Rgb(r, g, b)), bar, color::Fg(color::Reset),
+        "", pad=width.saturating_sub(bar_len)
+    )
+}
@@ -0,0 +1,140 @@
+extern crate termion;
+
+use self::termion::event::Key;
+use self::termion::input::TermRead;
+use self::termion::raw::IntoRawMode;
+use self::termion::screen::AlternateScreen;
+
+use std::io::{self, Write};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use push_swap::utils::N;
+use push_swap::stack::Stack;
+use push_swap::replay::Replay;
+use checker::read_instructions;
+
+use options::VisualizeConfig;
+
+mod draw;
+
+pub struct Player<S> {
+    pub replay: Replay<S>,
+    pub playing: bool,
+    // Instructions per second
+    pub speed: f32,
+    // Instruction index being typed after a `g`
+    pub jump_input: Option<String>,
+    pub value_range: (N, N),
+}
+
+pub fn visualize<S: Stack<N>>(config: VisualizeConfig) {
+    let VisualizeConfig { speed, raw_stack } = config;
+
+    let value_range = (
+        raw_stack.iter().cloned().min().unwrap_or(0),
+        raw_stack.iter().cloned().max().unwrap_or(0)
+    );
+
+    // Instructions are read from stdin so that controls have to be read
+    // from the terminal device itself
+    let instructions = read_instructions(io::stdin(), false)
+        .map(|instr| instr.expect("invalid instruction"))
+        .collect();
+
+    let mut player = Player {
+        replay: Replay::new(raw_stack.into_iter().collect::<S>(), instructions),
+        playing: false,
+        speed,
+        jump_input: None,
+        value_range,
+    };
+
+    let keys = spawn_key_reader();
+
+    let raw_stdout = io::stdout().into_raw_mode()
+        .expect("Failed to switch the terminal to raw mode");
+    let mut screen = AlternateScreen::from(raw_stdout);
+    write!(screen, "{}", termion::cursor::Hide).unwrap();
+
+    loop {
+        draw::frame(&mut screen, &player).expect("Failed to draw");
+
+        let key = match player.playing {
+            true => {
+                let delay = Duration::from_millis((1000. / player.speed) as u64);
+                match keys.recv_timeout(delay) {
+                    Ok(key)                               => Some(key),
+                    Err(mpsc::RecvTimeoutError::Timeout)  => None,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                }
+            },
+            false => match keys.recv() {
+                Ok(key) => Some(key),
+                Err(_)  => break,
+            }
+        };
+
+        match key {
+            None      => {
+                player.replay.forward();
+                player.playing = !player.replay.is_over();
+            },
+            Some(key) => if !handle_key(&mut player, key) { break }
+        }
+    }
+
+    write!(screen, "{}", termion::cursor::Show).unwrap();
+}
+
+// Returns whether the player should keep running
+fn handle_key<S: Stack<N>>(player: &mut Player<S>, key: Key) -> bool {
+    if let Some(mut input) = player.jump_input.take() {
+        match key {
+            Key::Char(c) if c.is_ascii_digit() => { input.push(c); player.jump_input = Some(input) },
+            Key::Backspace                 => { input.pop(); player.jump_input = Some(input) },
+            Key::Char('\n')                => {
+                if let Ok(target) = input.parse() { player.replay.seek(target) }
+            },
+            _ => ()
+        }
+        return true
+    }
+
+    match key {
+        Key::Char('q') | Key::Esc | Key::Ctrl('c') => return false,
+
+        Key::Char(' ') => player.playing = !player.playing && !player.replay.is_over(),
+
+        Key::Right | Key::Char('l') => { player.playing = false; player.replay.forward() },
+        Key::Left  | Key::Char('h') => { player.playing = false; player.replay.backward() },
+
+        Key::Up   | Key::Char('+') => player.speed = (player.speed * 2.).min(1000.),
+        Key::Down | Key::Char('-') => player.speed = (player.speed / 2.).max(0.5),
+
+        Key::Home | Key::Char('0') => player.replay.seek(0),
+        Key::End  | Key::Char('$') => { let end = player.replay.len(); player.replay.seek(end) },
+
+        Key::Char('g') => player.jump_input = Some(String::new()),
+
+        _ => ()
+    }
+
+    true
+}
+
+fn spawn_key_reader() -> mpsc::Receiver<Key> {
+    let tty = termion::get_tty()
+        .expect("Failed to open the terminal");
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for key in tty.keys() {
+            let sent = key.ok().map(|key| tx.send(key));
+            if let Some(Err(_)) = sent { return }
+        }
+    });
+
+    rx
+}